@@ -1,5 +1,11 @@
-#![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use crate::eventLogger::EventType;
+
+/// Number of analytics records stored per history chunk
+pub const HISTORY_CHUNK_SIZE: u32 = 50;
+
+/// Upper bound on the page size of history queries
+pub const MAX_PAGE_SIZE: u32 = 100;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -11,10 +17,80 @@ pub struct AnalyticsRecord {
     pub avg_progress_bps: u32, // Basis points (0-10000)
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AnalyticsScope {
+    Course(String),
+    Category(String),
+    Instructor(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopedAnalyticsRecord {
+    pub timestamp: u64,
+    pub enrollments: u64,
+    pub completions: u64,
+    pub avg_progress_bps: u32, // Basis points (0-10000)
+    pub revenue: u64, // in stroops
+}
+
+/// A scoped record derived from reporter counters; revenue is not tracked on-chain, so it has none
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopeSnapshot {
+    pub timestamp: u64,
+    pub enrollments: u64,
+    pub completions: u64,
+    pub avg_progress_bps: u32, // Basis points (0-10000)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReporterKind {
+    EventLogger,
+    Progress,
+    CourseMetadata,
+}
+
+/// Platform totals maintained by reporter contracts as the underlying actions happen
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformCounters {
+    pub users: u64, // distinct addresses seen making progress, completing or receiving credentials
+    pub courses: u64,
+    pub enrollments: u64,
+    pub completions: u64,
+    pub credentials_issued: u64,
+    pub progress_bps_total: u64, // sum of every enrollment's progress in basis points
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopeCounters {
+    pub enrollments: u64,
+    pub completions: u64,
+    pub progress_bps_total: u64,
+}
+
 #[contracttype]
 pub enum AnalyticsDataKey {
     Admin,
-    History,
+    History, // Single-vector history written by earlier versions, only read by `migrate_history`
+    HistoryChunk(u32),
+    HistoryLength, // records in `HistoryChunk`s, i.e. recorded since the upgrade
+    LegacyChunk(u32), // migrated legacy records, which come before every `HistoryChunk` record
+    LegacyLength,
+    ScopedChunk(AnalyticsScope, u32),
+    ScopedLength(AnalyticsScope),
+    Reporter(ReporterKind),
+    Counters,
+    ScopeCounters(AnalyticsScope),
+    SeenUser(Address),
+    SnapshotChunk(u32), // Records derived by `snapshot`, kept apart from admin-reported history
+    SnapshotLength,
+    ScopeSnapshotChunk(AnalyticsScope, u32),
+    ScopeSnapshotLength(AnalyticsScope),
 }
 
 #[contract]
@@ -28,23 +104,20 @@ impl AnalyticsContract {
             panic!("Already initialized");
         }
         env.storage().instance().set(&AnalyticsDataKey::Admin, &admin);
-        let history: Vec<AnalyticsRecord> = Vec::new(&env);
-        env.storage().instance().set(&AnalyticsDataKey::History, &history);
+        env.storage().instance().set(&AnalyticsDataKey::HistoryLength, &0u32);
     }
 
     /// Record new analytics data (Admin only)
     pub fn record_metrics(
-        env: Env, 
-        total_users: u64, 
-        total_courses: u64, 
-        total_completions: u64, 
+        env: Env,
+        total_users: u64,
+        total_courses: u64,
+        total_completions: u64,
         avg_progress_bps: u32
     ) {
         let admin: Address = env.storage().instance().get(&AnalyticsDataKey::Admin).unwrap();
         admin.require_auth();
 
-        let mut history: Vec<AnalyticsRecord> = env.storage().instance().get(&AnalyticsDataKey::History).unwrap_or(Vec::new(&env));
-        
         let record = AnalyticsRecord {
             timestamp: env.ledger().timestamp(),
             total_users,
@@ -53,22 +126,536 @@ impl AnalyticsContract {
             avg_progress_bps,
         };
 
-        history.push_back(record);
-        env.storage().instance().set(&AnalyticsDataKey::History, &history);
+        Self::append_record(env, record);
     }
 
-    /// Get the full history of analytics
+    /// Move up to `limit` records of the history written by earlier versions under the single `History` entry into chunks (Admin only)
+    ///
+    /// Resumes where the previous call stopped and returns how many legacy records are left. The legacy records predate
+    /// anything recorded since the upgrade, so they are placed first; each call shifts the cursors of newer records
+    pub fn migrate_history(env: Env, limit: u32) -> u32 {
+        let admin: Address = env.storage().instance().get(&AnalyticsDataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let legacy: Vec<AnalyticsRecord> = env.storage().instance()
+            .get(&AnalyticsDataKey::History)
+            .unwrap_or_else(|| panic!("No legacy history to migrate"));
+
+        let migrated = Self::get_legacy_length(env.clone());
+        let end = migrated.saturating_add(limit.min(MAX_PAGE_SIZE)).min(legacy.len());
+
+        let mut chunk_index = u32::MAX;
+        let mut chunk: Vec<AnalyticsRecord> = Vec::new(&env);
+        for index in migrated..end {
+            if index / HISTORY_CHUNK_SIZE != chunk_index {
+                if chunk_index != u32::MAX {
+                    env.storage().persistent().set(&AnalyticsDataKey::LegacyChunk(chunk_index), &chunk);
+                }
+                chunk_index = index / HISTORY_CHUNK_SIZE;
+                chunk = env.storage().persistent()
+                    .get(&AnalyticsDataKey::LegacyChunk(chunk_index))
+                    .unwrap_or(Vec::new(&env));
+            }
+            chunk.push_back(legacy.get(index).unwrap());
+        }
+        if chunk_index != u32::MAX {
+            env.storage().persistent().set(&AnalyticsDataKey::LegacyChunk(chunk_index), &chunk);
+        }
+        env.storage().instance().set(&AnalyticsDataKey::LegacyLength, &end);
+
+        if end == legacy.len() {
+            env.storage().instance().remove(&AnalyticsDataKey::History);
+        }
+
+        legacy.len() - end
+    }
+
+    /// Get the number of analytics records
+    pub fn get_history_length(env: Env) -> u32 {
+        Self::get_legacy_length(env.clone()) + Self::get_recorded_length(env)
+    }
+
+    /// Get the oldest records, up to `MAX_PAGE_SIZE`; kept for callers of the single-vector API, use `get_history_page` for the rest
     pub fn get_history(env: Env) -> Vec<AnalyticsRecord> {
-        env.storage().instance().get(&AnalyticsDataKey::History).unwrap_or(Vec::new(&env))
+        Self::get_history_page(env, 0, MAX_PAGE_SIZE)
+    }
+
+    /// Get up to `limit` analytics records starting at `cursor`, oldest first
+    pub fn get_history_page(env: Env, cursor: u32, limit: u32) -> Vec<AnalyticsRecord> {
+        let length = Self::get_history_length(env.clone());
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(length);
+
+        let mut records = Vec::new(&env);
+        let mut chunk_start = u32::MAX;
+        let mut chunk: Vec<AnalyticsRecord> = Vec::new(&env);
+        for index in cursor..end {
+            let (key, offset) = Self::record_location(env.clone(), index);
+            if index - offset != chunk_start {
+                chunk_start = index - offset;
+                chunk = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+            }
+            records.push_back(chunk.get(offset).unwrap());
+        }
+
+        records
+    }
+
+    /// Get the cursor of the first record at or after `timestamp`, for paging through a time range
+    pub fn get_cursor_at(env: Env, timestamp: u64) -> u32 {
+        // Records are appended in ledger order, so timestamps never decrease
+        let mut low = 0u32;
+        let mut high = Self::get_history_length(env.clone());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if Self::get_record(env.clone(), middle).timestamp < timestamp {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        low
+    }
+
+    /// Get up to `limit` records with `start_time <= timestamp < end_time`, oldest first
+    pub fn get_history_range(env: Env, start_time: u64, end_time: u64, limit: u32) -> Vec<AnalyticsRecord> {
+        let cursor = Self::get_cursor_at(env.clone(), start_time);
+
+        let mut records = Vec::new(&env);
+        for record in Self::get_history_page(env.clone(), cursor, limit).iter() {
+            if record.timestamp >= end_time {
+                break;
+            }
+            records.push_back(record);
+        }
+
+        records
+    }
+
+    /// Record analytics for a course, category or instructor (Admin only)
+    pub fn record_scoped_metrics(
+        env: Env,
+        scope: AnalyticsScope,
+        enrollments: u64,
+        completions: u64,
+        avg_progress_bps: u32,
+        revenue: u64,
+    ) {
+        let admin: Address = env.storage().instance().get(&AnalyticsDataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if avg_progress_bps > 10000 {
+            panic!("Progress must be 0-10000 basis points");
+        }
+
+        let record = ScopedAnalyticsRecord {
+            timestamp: env.ledger().timestamp(),
+            enrollments,
+            completions,
+            avg_progress_bps,
+            revenue,
+        };
+
+        Self::append_scoped_record(env, scope, record);
+    }
+
+    /// Get the number of records in a scoped series
+    pub fn get_scoped_history_length(env: Env, scope: AnalyticsScope) -> u32 {
+        env.storage().persistent().get(&AnalyticsDataKey::ScopedLength(scope)).unwrap_or(0)
+    }
+
+    /// Get up to `limit` records of a scoped series starting at `cursor`, oldest first
+    pub fn get_scoped_history_page(env: Env, scope: AnalyticsScope, cursor: u32, limit: u32) -> Vec<ScopedAnalyticsRecord> {
+        let length = Self::get_scoped_history_length(env.clone(), scope.clone());
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(length);
+
+        let mut records = Vec::new(&env);
+        let mut chunk_index = u32::MAX;
+        let mut chunk: Vec<ScopedAnalyticsRecord> = Vec::new(&env);
+        for index in cursor..end {
+            if index / HISTORY_CHUNK_SIZE != chunk_index {
+                chunk_index = index / HISTORY_CHUNK_SIZE;
+                chunk = env.storage().persistent()
+                    .get(&AnalyticsDataKey::ScopedChunk(scope.clone(), chunk_index))
+                    .unwrap_or(Vec::new(&env));
+            }
+            records.push_back(chunk.get(index % HISTORY_CHUNK_SIZE).unwrap());
+        }
+
+        records
+    }
+
+    /// Get the cursor of the first record of a scoped series at or after `timestamp`
+    pub fn get_scoped_cursor_at(env: Env, scope: AnalyticsScope, timestamp: u64) -> u32 {
+        let mut low = 0u32;
+        let mut high = Self::get_scoped_history_length(env.clone(), scope.clone());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if Self::get_scoped_record(env.clone(), scope.clone(), middle).timestamp < timestamp {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        low
+    }
+
+    /// Get up to `limit` records of a scoped series with `start_time <= timestamp < end_time`, oldest first
+    pub fn get_scoped_history_range(
+        env: Env,
+        scope: AnalyticsScope,
+        start_time: u64,
+        end_time: u64,
+        limit: u32,
+    ) -> Vec<ScopedAnalyticsRecord> {
+        let cursor = Self::get_scoped_cursor_at(env.clone(), scope.clone(), start_time);
+
+        let mut records = Vec::new(&env);
+        for record in Self::get_scoped_history_page(env.clone(), scope, cursor, limit).iter() {
+            if record.timestamp >= end_time {
+                break;
+            }
+            records.push_back(record);
+        }
+
+        records
+    }
+
+    /// Get the most recent record of a scoped series
+    pub fn get_scoped_latest(env: Env, scope: AnalyticsScope) -> Option<ScopedAnalyticsRecord> {
+        let length = Self::get_scoped_history_length(env.clone(), scope.clone());
+        if length == 0 {
+            None
+        } else {
+            Some(Self::get_scoped_record(env, scope, length - 1))
+        }
+    }
+
+    /// Register the contract allowed to report one kind of activity (Admin only)
+    pub fn set_reporter(env: Env, kind: ReporterKind, contract: Address) {
+        let admin: Address = env.storage().instance().get(&AnalyticsDataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&AnalyticsDataKey::Reporter(kind), &contract);
+    }
+
+    /// Get the contract registered for a reporter kind
+    pub fn get_reporter(env: Env, kind: ReporterKind) -> Option<Address> {
+        env.storage().instance().get(&AnalyticsDataKey::Reporter(kind))
+    }
+
+    /// Count a newly created course (CourseMetadataContract only)
+    pub fn on_course_created(env: Env) {
+        Self::require_reporter(env.clone(), ReporterKind::CourseMetadata);
+
+        let mut counters = Self::get_counters(env.clone());
+        counters.courses += 1;
+        env.storage().persistent().set(&AnalyticsDataKey::Counters, &counters);
+    }
+
+    /// Count a verified course completion (CourseMetadataContract only)
+    pub fn on_course_completed(env: Env, student: Address, course_id: String, instructor: Address, category: String) {
+        Self::require_reporter(env.clone(), ReporterKind::CourseMetadata);
+
+        let mut counters = Self::get_counters(env.clone());
+        Self::mark_user_seen(env.clone(), student, &mut counters);
+        counters.completions += 1;
+        env.storage().persistent().set(&AnalyticsDataKey::Counters, &counters);
+
+        for scope in Self::course_scopes(env.clone(), course_id, instructor, category).iter() {
+            let mut scope_counters = Self::get_scope_counters(env.clone(), scope.clone());
+            scope_counters.completions += 1;
+            env.storage().persistent().set(&AnalyticsDataKey::ScopeCounters(scope), &scope_counters);
+        }
+    }
+
+    /// Track a learner's progress in a course; `previous_bps` is None for a new enrollment (CourseProgressContract only)
+    pub fn on_progress(
+        env: Env,
+        user: Address,
+        course_id: String,
+        instructor: Address,
+        category: String,
+        previous_bps: Option<u32>,
+        progress_bps: u32,
+    ) {
+        Self::require_reporter(env.clone(), ReporterKind::Progress);
+
+        let previous = previous_bps.unwrap_or(0) as u64;
+        let mut counters = Self::get_counters(env.clone());
+        Self::mark_user_seen(env.clone(), user, &mut counters);
+        if previous_bps.is_none() {
+            counters.enrollments += 1;
+        }
+        counters.progress_bps_total = counters.progress_bps_total - previous + progress_bps as u64;
+        env.storage().persistent().set(&AnalyticsDataKey::Counters, &counters);
+
+        for scope in Self::course_scopes(env.clone(), course_id, instructor, category).iter() {
+            let mut scope_counters = Self::get_scope_counters(env.clone(), scope.clone());
+            if previous_bps.is_none() {
+                scope_counters.enrollments += 1;
+            }
+            scope_counters.progress_bps_total = scope_counters.progress_bps_total - previous + progress_bps as u64;
+            env.storage().persistent().set(&AnalyticsDataKey::ScopeCounters(scope), &scope_counters);
+        }
+    }
+
+    /// Count an issued credential and its recipient (EventLoggerContract only)
+    ///
+    /// Other event types are logged on the user's own say-so, so they are not counted
+    pub fn on_event(env: Env, user: Address, event_type: EventType) {
+        Self::require_reporter(env.clone(), ReporterKind::EventLogger);
+
+        if event_type != EventType::CredentialIssuance {
+            return;
+        }
+
+        let mut counters = Self::get_counters(env.clone());
+        Self::mark_user_seen(env.clone(), user, &mut counters);
+        counters.credentials_issued += 1;
+        env.storage().persistent().set(&AnalyticsDataKey::Counters, &counters);
+    }
+
+    /// Get the reporter-maintained platform counters
+    pub fn get_counters(env: Env) -> PlatformCounters {
+        env.storage().persistent()
+            .get(&AnalyticsDataKey::Counters)
+            .unwrap_or(PlatformCounters {
+                users: 0,
+                courses: 0,
+                enrollments: 0,
+                completions: 0,
+                credentials_issued: 0,
+                progress_bps_total: 0,
+            })
+    }
+
+    /// Get the reporter-maintained counters of a course, category or instructor
+    pub fn get_scope_counters(env: Env, scope: AnalyticsScope) -> ScopeCounters {
+        env.storage().persistent()
+            .get(&AnalyticsDataKey::ScopeCounters(scope))
+            .unwrap_or(ScopeCounters {
+                enrollments: 0,
+                completions: 0,
+                progress_bps_total: 0,
+            })
+    }
+
+    /// Append a platform record computed from the counters to the snapshot series; anyone may call it, once per ledger timestamp
+    pub fn snapshot(env: Env) -> AnalyticsRecord {
+        let timestamp = env.ledger().timestamp();
+        if Self::get_latest_snapshot(env.clone()).is_some_and(|latest| latest.timestamp == timestamp) {
+            panic!("Snapshot already taken");
+        }
+
+        let counters = Self::get_counters(env.clone());
+        let record = AnalyticsRecord {
+            timestamp,
+            total_users: counters.users,
+            total_courses: counters.courses,
+            total_completions: counters.completions,
+            avg_progress_bps: counters.progress_bps_total.checked_div(counters.enrollments).unwrap_or(0) as u32,
+        };
+
+        let length = Self::get_snapshot_length(env.clone());
+        let chunk_key = AnalyticsDataKey::SnapshotChunk(length / HISTORY_CHUNK_SIZE);
+        let mut chunk: Vec<AnalyticsRecord> = env.storage().persistent().get(&chunk_key).unwrap_or(Vec::new(&env));
+        chunk.push_back(record.clone());
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&AnalyticsDataKey::SnapshotLength, &(length + 1));
+
+        record
+    }
+
+    /// Get the number of records in the snapshot series
+    pub fn get_snapshot_length(env: Env) -> u32 {
+        env.storage().persistent().get(&AnalyticsDataKey::SnapshotLength).unwrap_or(0)
+    }
+
+    /// Get up to `limit` records of the snapshot series starting at `cursor`, oldest first
+    pub fn get_snapshot_page(env: Env, cursor: u32, limit: u32) -> Vec<AnalyticsRecord> {
+        let length = Self::get_snapshot_length(env.clone());
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(length);
+
+        let mut records = Vec::new(&env);
+        let mut chunk_index = u32::MAX;
+        let mut chunk: Vec<AnalyticsRecord> = Vec::new(&env);
+        for index in cursor..end {
+            if index / HISTORY_CHUNK_SIZE != chunk_index {
+                chunk_index = index / HISTORY_CHUNK_SIZE;
+                chunk = env.storage().persistent()
+                    .get(&AnalyticsDataKey::SnapshotChunk(chunk_index))
+                    .unwrap_or(Vec::new(&env));
+            }
+            records.push_back(chunk.get(index % HISTORY_CHUNK_SIZE).unwrap());
+        }
+
+        records
+    }
+
+    /// Get the most recent record of the snapshot series
+    pub fn get_latest_snapshot(env: Env) -> Option<AnalyticsRecord> {
+        let length = Self::get_snapshot_length(env.clone());
+        if length == 0 {
+            None
+        } else {
+            Self::get_snapshot_page(env, length - 1, 1).get(0)
+        }
+    }
+
+    /// Append a scoped record computed from the counters to the scope's snapshot series; anyone may call it, once per ledger timestamp
+    pub fn snapshot_scope(env: Env, scope: AnalyticsScope) -> ScopeSnapshot {
+        let timestamp = env.ledger().timestamp();
+        if Self::get_latest_scope_snapshot(env.clone(), scope.clone()).is_some_and(|latest| latest.timestamp == timestamp) {
+            panic!("Snapshot already taken");
+        }
+
+        let counters = Self::get_scope_counters(env.clone(), scope.clone());
+        let record = ScopeSnapshot {
+            timestamp,
+            enrollments: counters.enrollments,
+            completions: counters.completions,
+            avg_progress_bps: counters.progress_bps_total.checked_div(counters.enrollments).unwrap_or(0) as u32,
+        };
+
+        let length = Self::get_scope_snapshot_length(env.clone(), scope.clone());
+        let chunk_key = AnalyticsDataKey::ScopeSnapshotChunk(scope.clone(), length / HISTORY_CHUNK_SIZE);
+        let mut chunk: Vec<ScopeSnapshot> = env.storage().persistent().get(&chunk_key).unwrap_or(Vec::new(&env));
+        chunk.push_back(record.clone());
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&AnalyticsDataKey::ScopeSnapshotLength(scope), &(length + 1));
+
+        record
+    }
+
+    /// Get the number of records in a scope's snapshot series
+    pub fn get_scope_snapshot_length(env: Env, scope: AnalyticsScope) -> u32 {
+        env.storage().persistent().get(&AnalyticsDataKey::ScopeSnapshotLength(scope)).unwrap_or(0)
+    }
+
+    /// Get up to `limit` records of a scope's snapshot series starting at `cursor`, oldest first
+    pub fn get_scope_snapshot_page(env: Env, scope: AnalyticsScope, cursor: u32, limit: u32) -> Vec<ScopeSnapshot> {
+        let length = Self::get_scope_snapshot_length(env.clone(), scope.clone());
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(length);
+
+        let mut records = Vec::new(&env);
+        let mut chunk_index = u32::MAX;
+        let mut chunk: Vec<ScopeSnapshot> = Vec::new(&env);
+        for index in cursor..end {
+            if index / HISTORY_CHUNK_SIZE != chunk_index {
+                chunk_index = index / HISTORY_CHUNK_SIZE;
+                chunk = env.storage().persistent()
+                    .get(&AnalyticsDataKey::ScopeSnapshotChunk(scope.clone(), chunk_index))
+                    .unwrap_or(Vec::new(&env));
+            }
+            records.push_back(chunk.get(index % HISTORY_CHUNK_SIZE).unwrap());
+        }
+
+        records
+    }
+
+    /// Get the most recent record of a scope's snapshot series
+    pub fn get_latest_scope_snapshot(env: Env, scope: AnalyticsScope) -> Option<ScopeSnapshot> {
+        let length = Self::get_scope_snapshot_length(env.clone(), scope.clone());
+        if length == 0 {
+            None
+        } else {
+            Self::get_scope_snapshot_page(env, scope, length - 1, 1).get(0)
+        }
     }
-    
+
     /// Get the most recent analytics record
     pub fn get_latest(env: Env) -> Option<AnalyticsRecord> {
-        let history: Vec<AnalyticsRecord> = env.storage().instance().get(&AnalyticsDataKey::History).unwrap_or(Vec::new(&env));
-        if history.is_empty() {
+        let length = Self::get_history_length(env.clone());
+        if length == 0 {
             None
         } else {
-            Some(history.get(history.len() - 1).unwrap())
+            Some(Self::get_record(env, length - 1))
         }
     }
-}
\ No newline at end of file
+
+    /// Append a record to the platform history; only the newest chunk is rewritten, so the cost stays flat as history grows
+    fn append_record(env: Env, record: AnalyticsRecord) {
+        let length = Self::get_recorded_length(env.clone());
+        let chunk_key = AnalyticsDataKey::HistoryChunk(length / HISTORY_CHUNK_SIZE);
+        let mut chunk: Vec<AnalyticsRecord> = env.storage().persistent().get(&chunk_key).unwrap_or(Vec::new(&env));
+
+        chunk.push_back(record);
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().instance().set(&AnalyticsDataKey::HistoryLength, &(length + 1));
+    }
+
+    /// Append a record to a scoped series
+    fn append_scoped_record(env: Env, scope: AnalyticsScope, record: ScopedAnalyticsRecord) {
+        let length = Self::get_scoped_history_length(env.clone(), scope.clone());
+        let chunk_key = AnalyticsDataKey::ScopedChunk(scope.clone(), length / HISTORY_CHUNK_SIZE);
+        let mut chunk: Vec<ScopedAnalyticsRecord> = env.storage().persistent().get(&chunk_key).unwrap_or(Vec::new(&env));
+
+        chunk.push_back(record);
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&AnalyticsDataKey::ScopedLength(scope), &(length + 1));
+    }
+
+    /// Require authorization from the contract registered for a reporter kind
+    fn require_reporter(env: Env, kind: ReporterKind) {
+        let reporter: Address = env.storage().instance()
+            .get(&AnalyticsDataKey::Reporter(kind))
+            .unwrap_or_else(|| panic!("Reporter not configured"));
+        reporter.require_auth();
+    }
+
+    /// Count an address as a platform user the first time it is seen
+    fn mark_user_seen(env: Env, user: Address, counters: &mut PlatformCounters) {
+        let seen_key = AnalyticsDataKey::SeenUser(user);
+        if !env.storage().persistent().has(&seen_key) {
+            env.storage().persistent().set(&seen_key, &true);
+            counters.users += 1;
+        }
+    }
+
+    /// Scopes a course's numbers roll up into
+    fn course_scopes(env: Env, course_id: String, instructor: Address, category: String) -> Vec<AnalyticsScope> {
+        let mut scopes = Vec::new(&env);
+        scopes.push_back(AnalyticsScope::Course(course_id));
+        scopes.push_back(AnalyticsScope::Instructor(instructor));
+        scopes.push_back(AnalyticsScope::Category(category));
+        scopes
+    }
+
+    /// Load a single record of a scoped series by index
+    fn get_scoped_record(env: Env, scope: AnalyticsScope, index: u32) -> ScopedAnalyticsRecord {
+        let chunk: Vec<ScopedAnalyticsRecord> = env.storage().persistent()
+            .get(&AnalyticsDataKey::ScopedChunk(scope, index / HISTORY_CHUNK_SIZE))
+            .unwrap_or(Vec::new(&env));
+        chunk.get(index % HISTORY_CHUNK_SIZE).unwrap_or_else(|| panic!("Record not found"))
+    }
+
+    /// Load a single record by index
+    fn get_record(env: Env, index: u32) -> AnalyticsRecord {
+        let (key, offset) = Self::record_location(env.clone(), index);
+        let chunk: Vec<AnalyticsRecord> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        chunk.get(offset).unwrap_or_else(|| panic!("Record not found"))
+    }
+
+    /// The chunk holding a record and its position within it; migrated legacy records come first
+    fn record_location(env: Env, index: u32) -> (AnalyticsDataKey, u32) {
+        let legacy_length = Self::get_legacy_length(env);
+        if index < legacy_length {
+            (AnalyticsDataKey::LegacyChunk(index / HISTORY_CHUNK_SIZE), index % HISTORY_CHUNK_SIZE)
+        } else {
+            let index = index - legacy_length;
+            (AnalyticsDataKey::HistoryChunk(index / HISTORY_CHUNK_SIZE), index % HISTORY_CHUNK_SIZE)
+        }
+    }
+
+    /// Number of legacy records migrated so far
+    fn get_legacy_length(env: Env) -> u32 {
+        env.storage().instance().get(&AnalyticsDataKey::LegacyLength).unwrap_or(0)
+    }
+
+    /// Number of records appended since the upgrade
+    fn get_recorded_length(env: Env) -> u32 {
+        env.storage().instance().get(&AnalyticsDataKey::HistoryLength).unwrap_or(0)
+    }
+}
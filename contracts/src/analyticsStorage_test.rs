@@ -1,8 +1,11 @@
 #![cfg(test)]
 extern crate std;
 
-use crate::analyticsStorage::{AnalyticsContract, AnalyticsContractClient};
-use soroban_sdk::{testutils::{Address as _, Ledger}, Env, Address};
+use crate::analyticsStorage::{AnalyticsContract, AnalyticsContractClient, AnalyticsDataKey, AnalyticsRecord, AnalyticsScope, ReporterKind, MAX_PAGE_SIZE};
+use crate::eventLogger::{EventLoggerContract, EventLoggerContractClient};
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, NewCourse};
+use crate::progress::{CourseProgressContract, CourseProgressContractClient};
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Env, Address, String, Vec};
 
 #[test]
 fn test_analytics_flow() {
@@ -34,7 +37,256 @@ fn test_analytics_flow() {
     client.record_metrics(&110, &6, &25, &7600);
 
     // Verify history
-    let history = client.get_history();
+    let history = client.get_history_page(&0, &10);
     assert_eq!(history.len(), 2);
     assert_eq!(history.get(1).unwrap().timestamp, 1000);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_history_pages_and_time_ranges() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    // Enough records to span several chunks, one every 100 seconds
+    for index in 0..120u64 {
+        env.ledger().set_timestamp(index * 100);
+        client.record_metrics(&index, &1, &0, &0);
+    }
+    assert_eq!(client.get_history_length(), 120);
+
+    // Pages cross chunk boundaries and are capped
+    let page = client.get_history_page(&45, &10);
+    assert_eq!(page.len(), 10);
+    assert_eq!(page.get(0).unwrap().total_users, 45);
+    assert_eq!(page.get(9).unwrap().total_users, 54);
+    assert_eq!(client.get_history_page(&0, &500).len(), MAX_PAGE_SIZE);
+    assert_eq!(client.get_history_page(&115, &10).len(), 5);
+
+    // Time ranges are half-open
+    assert_eq!(client.get_cursor_at(&5050), 51);
+    let range = client.get_history_range(&5000, &6000, &100);
+    assert_eq!(range.len(), 10);
+    assert_eq!(range.get(0).unwrap().timestamp, 5000);
+    assert_eq!(range.get(9).unwrap().timestamp, 5900);
+
+    assert_eq!(client.get_latest().unwrap().total_users, 119);
+}
+
+#[test]
+fn test_migrate_legacy_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    // History left behind by the single-vector layout, one record every 100 seconds
+    let mut legacy: Vec<AnalyticsRecord> = Vec::new(&env);
+    for index in 0..60u64 {
+        legacy.push_back(AnalyticsRecord {
+            timestamp: index * 100,
+            total_users: index,
+            total_courses: 1,
+            total_completions: 0,
+            avg_progress_bps: 0,
+        });
+    }
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&AnalyticsDataKey::History, &legacy);
+    });
+
+    // A record taken after the upgrade but before the migration
+    env.ledger().set_timestamp(10_000);
+    client.record_metrics(&60, &1, &0, &0);
+
+    // Migrated in batches; the records moved so far already sit in front of newer ones
+    assert_eq!(client.migrate_history(&35), 25);
+    assert_eq!(client.get_history_length(), 36);
+    assert_eq!(client.get_history_page(&34, &2).get(1).unwrap().timestamp, 10_000);
+    client.record_metrics(&61, &1, &0, &0);
+    assert_eq!(client.migrate_history(&35), 0);
+
+    assert_eq!(client.get_history_length(), 62);
+    assert_eq!(client.get_history_page(&0, &1).get(0).unwrap().timestamp, 0);
+    assert_eq!(client.get_history().len(), 62);
+    assert_eq!(client.get_history_page(&59, &2).get(1).unwrap().timestamp, 10_000);
+    assert_eq!(client.get_cursor_at(&5_000), 50);
+    assert_eq!(client.get_latest().unwrap().total_users, 61);
+
+    // The legacy entry is gone, so the migration only runs once
+    assert!(client.try_migrate_history(&35).is_err());
+}
+
+#[test]
+fn test_scoped_series() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let instructor = Address::generate(&env);
+    let course = AnalyticsScope::Course(String::from_str(&env, "course_1"));
+    let own_numbers = AnalyticsScope::Instructor(instructor.clone());
+
+    env.ledger().set_timestamp(100);
+    client.record_scoped_metrics(&course, &40, &5, &3200, &4_000_000);
+    client.record_scoped_metrics(&own_numbers, &90, &12, &4100, &9_000_000);
+    env.ledger().set_timestamp(200);
+    client.record_scoped_metrics(&course, &45, &8, &3900, &4_500_000);
+
+    // Series are kept apart
+    assert_eq!(client.get_scoped_history_length(&course), 2);
+    assert_eq!(client.get_scoped_history_length(&own_numbers), 1);
+    assert_eq!(client.get_scoped_history_length(&AnalyticsScope::Category(String::from_str(&env, "Programming"))), 0);
+    assert_eq!(client.get_history_length(), 0);
+
+    let latest = client.get_scoped_latest(&course).unwrap();
+    assert_eq!(latest.enrollments, 45);
+    assert_eq!(latest.revenue, 4_500_000);
+
+    let range = client.get_scoped_history_range(&course, &150, &300, &10);
+    assert_eq!(range.len(), 1);
+    assert_eq!(range.get(0).unwrap().completions, 8);
+    assert_eq!(client.get_scoped_history_page(&own_numbers, &0, &10).get(0).unwrap().avg_progress_bps, 4100);
+}
+
+#[test]
+fn test_counters_derived_from_reporters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+
+    let analytics = AnalyticsContractClient::new(&env, &env.register_contract(None, AnalyticsContract));
+    analytics.initialize(&admin);
+
+    let courses = CourseMetadataContractClient::new(&env, &env.register_contract(None, CourseMetadataContract));
+    courses.initialize(&admin);
+    courses.set_analytics_contract(&admin, &analytics.address);
+
+    let progress = CourseProgressContractClient::new(&env, &env.register_contract(None, CourseProgressContract));
+    progress.initialize(&admin, &courses.address);
+    progress.set_analytics_contract(&admin, &analytics.address);
+
+    analytics.set_reporter(&ReporterKind::CourseMetadata, &courses.address);
+    analytics.set_reporter(&ReporterKind::Progress, &progress.address);
+
+    let instructor = Address::generate(&env);
+    let category = String::from_str(&env, "Programming");
+    let course_id = courses.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Stellar Basics"),
+            description: String::from_str(&env, "Accounts, assets and transactions"),
+            category: category.clone(),
+            level: String::from_str(&env, "beginner"),
+            duration: 10,
+            price: 0,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmSyllabus"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+    courses.set_course_lessons(&course_id, &instructor, &2);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    progress.record_progress(&first, &course_id, &1);
+    progress.record_progress(&first, &course_id, &2);
+    progress.record_progress(&second, &course_id, &1);
+    let completion_id = courses.record_completion(&course_id, &first, &90, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+
+    // Completions only count once verified
+    assert_eq!(analytics.get_counters().completions, 0);
+    courses.verify_completion(&completion_id, &instructor);
+
+    let counters = analytics.get_counters();
+    assert_eq!(counters.users, 2);
+    assert_eq!(counters.courses, 1);
+    assert_eq!(counters.enrollments, 2);
+    assert_eq!(counters.completions, 1);
+    assert_eq!(counters.progress_bps_total, 15000);
+
+    env.ledger().set_timestamp(1000);
+    let record = analytics.snapshot();
+    assert_eq!(record.total_users, 2);
+    assert_eq!(record.total_completions, 1);
+    assert_eq!(record.avg_progress_bps, 7500);
+    assert_eq!(analytics.get_latest_snapshot().unwrap().timestamp, 1000);
+
+    // Derived records stay out of the admin-reported history, which can still be written at the same timestamp
+    assert_eq!(analytics.get_history_length(), 0);
+    analytics.record_metrics(&50, &3, &10, &6000);
+    assert_eq!(analytics.get_latest().unwrap().total_users, 50);
+    assert_eq!(analytics.get_snapshot_length(), 1);
+    assert!(analytics.try_snapshot().is_err());
+
+    env.ledger().set_timestamp(2000);
+    analytics.record_metrics(&60, &3, &12, &6100);
+    assert_eq!(analytics.snapshot().total_users, 2);
+    assert_eq!(analytics.get_snapshot_page(&0, &10).len(), 2);
+
+    let scope = AnalyticsScope::Instructor(instructor);
+    analytics.record_scoped_metrics(&scope, &5, &1, &4000, &25_000);
+    let scoped = analytics.snapshot_scope(&scope);
+    assert_eq!(scoped.enrollments, 2);
+    assert_eq!(scoped.completions, 1);
+    assert_eq!(analytics.get_latest_scope_snapshot(&scope).unwrap(), scoped);
+    assert_eq!(analytics.get_scoped_latest(&scope).unwrap().revenue, 25_000);
+    assert_eq!(analytics.get_scoped_history_length(&scope), 1);
+    assert_eq!(analytics.get_scope_counters(&AnalyticsScope::Category(category)).enrollments, 2);
+}
+
+#[test]
+fn test_only_issued_credentials_count_users() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+
+    let analytics = AnalyticsContractClient::new(&env, &env.register_contract(None, AnalyticsContract));
+    analytics.initialize(&admin);
+
+    let events = EventLoggerContractClient::new(&env, &env.register_contract(None, EventLoggerContract));
+    events.initialize(&admin);
+    events.set_analytics_contract(&admin, &analytics.address);
+    analytics.set_reporter(&ReporterKind::EventLogger, &events.address);
+
+    // Self-reported events do not make an address a platform user
+    let metadata = String::from_str(&env, "{}");
+    let course_id = String::from_str(&env, "course-101");
+    events.log_profile_update(&Address::generate(&env), &metadata);
+    events.log_course_enrollment(&Address::generate(&env), &course_id, &metadata);
+    events.log_course_completion(&Address::generate(&env), &course_id, &metadata);
+    assert_eq!(analytics.get_counters().users, 0);
+
+    let learner = Address::generate(&env);
+    events.log_credential_issuance(&admin, &learner, &1, &course_id, &metadata);
+    events.log_credential_issuance(&admin, &learner, &2, &course_id, &metadata);
+
+    let counters = analytics.get_counters();
+    assert_eq!(counters.users, 1);
+    assert_eq!(counters.credentials_issued, 2);
+}
+
+#[test]
+#[should_panic(expected = "Reporter not configured")]
+fn test_hooks_require_configured_reporter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let analytics = AnalyticsContractClient::new(&env, &env.register_contract(None, AnalyticsContract));
+    analytics.initialize(&Address::generate(&env));
+
+    analytics.on_course_created();
+}
@@ -1,5 +1,9 @@
-#![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec, Map, Symbol, U256};
+use soroban_sdk::{contract, contractimpl, contracttype, xdr::ToXdr, Address, BytesN, Env, String, Vec};
+use crate::numbered_id;
+use crate::StarkEdContractClient;
+use crate::eventLogger::EventLoggerContractClient;
+use crate::analyticsStorage::AnalyticsContractClient;
+use crate::progress::CourseProgressContractClient;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -10,6 +14,15 @@ pub enum CourseStatus {
     Suspended,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CreditSystem {
+    None,
+    CreditHour,
+    Ects,
+    Ceu,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct CourseMetadata {
@@ -36,6 +49,133 @@ pub struct CourseMetadata {
     pub created_at: u64,
     pub updated_at: u64,
     pub verification_hash: String, // SHA-256 hash for integrity
+    pub credit_value: u32, // hundredths of a credit, e.g. 250 = 2.5 ECTS
+    pub credit_system: CreditSystem,
+    pub lesson_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CurriculumModule {
+    pub id: u32,
+    pub title: String,
+    pub lesson_ids: Vec<u32>, // in teaching order
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Lesson {
+    pub id: u32, // sequential per course, doubles as the progress bitmap index
+    pub module_id: u32,
+    pub title: String,
+    pub content_cid: String, // IPFS CID of the lesson content
+    pub is_required: bool,
+    pub estimated_minutes: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DeadlineStatus {
+    SelfPaced, // the learner is not in a cohort, so there is no due date
+    OnTime,
+    WithinGrace, // after the due date but inside the grace period, no penalty
+    Late, // after the grace period, penalty applies
+    PastCutoff, // after the hard cutoff
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LatePolicy {
+    pub grace_period: u64, // seconds after a due date before the penalty applies
+    pub penalty_percent: u32, // 0-100, deducted from the grade of late work
+    pub hard_cutoff: Option<u64>, // seconds after a due date after which work is no longer accepted
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LessonDueDate {
+    pub lesson_id: u32,
+    pub due_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Cohort {
+    pub id: u32,
+    pub course_id: String,
+    pub start_date: u64,
+    pub end_date: u64, // due date for completing the course
+    pub due_dates: Vec<LessonDueDate>,
+    pub late_policy: LatePolicy,
+    pub learner_count: u32,
+}
+
+impl Cohort {
+    /// Due date of a lesson, falling back to the cohort end date; `None` is the course itself
+    pub fn due_date(&self, lesson_id: Option<u32>) -> u64 {
+        lesson_id
+            .and_then(|lesson_id| self.due_dates.iter().find(|due_date| due_date.lesson_id == lesson_id))
+            .map(|due_date| due_date.due_at)
+            .unwrap_or(self.end_date)
+    }
+
+    /// Classify work submitted at `submitted_at` against a lesson's or the course's due date
+    pub fn deadline_status(&self, lesson_id: Option<u32>, submitted_at: u64) -> DeadlineStatus {
+        let due_at = self.due_date(lesson_id);
+
+        if submitted_at <= due_at {
+            DeadlineStatus::OnTime
+        } else if self.late_policy.hard_cutoff.is_some_and(|cutoff| submitted_at > due_at.saturating_add(cutoff)) {
+            DeadlineStatus::PastCutoff
+        } else if submitted_at <= due_at.saturating_add(self.late_policy.grace_period) {
+            DeadlineStatus::WithinGrace
+        } else {
+            DeadlineStatus::Late
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GradeComponentKind {
+    Quiz,
+    Assignment,
+    Exam,
+    Participation,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GradeRounding {
+    Down,
+    Nearest, // halves round up
+    Up,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GradeComponent {
+    pub id: u32,
+    pub name: String,
+    pub kind: GradeComponentKind,
+    pub weight: u32, // percent of the final grade; a gradebook's weights sum to 100
+    pub max_score: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Gradebook {
+    pub components: Vec<GradeComponent>,
+    pub rounding: GradeRounding,
+    pub pass_mark: u32, // 0-100
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScoreEntry {
+    pub score: u32, // 0-max_score of the component
+    pub graded_by: Address,
+    pub graded_at: u64,
 }
 
 #[contracttype]
@@ -49,6 +189,51 @@ pub struct CourseCompletion {
     pub certificate_hash: String, // IPFS hash of certificate
     pub is_verified: bool,
     pub skills_acquired: Vec<String>,
+    pub credits: u32, // hundredths of a credit, copied from the course at completion
+    pub credit_system: CreditSystem,
+    pub credential_id: Option<u64>, // StarkEdContract credential issued for this completion
+    pub cohort_id: Option<u32>,
+    pub deadline_status: DeadlineStatus,
+}
+
+/// The instructor-supplied fields of a new course
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NewCourse {
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub level: String, // beginner, intermediate, advanced
+    pub duration: u64, // in hours
+    pub price: u64, // in stroops
+    pub prerequisites: Vec<String>,
+    pub learning_objectives: Vec<String>,
+    pub syllabus: String, // IPFS hash or JSON string
+    pub thumbnail_url: String,
+    pub tags: Vec<String>,
+    pub language: String,
+    pub certificate_enabled: bool,
+    pub max_students: u64,
+}
+
+/// Changes to a course's metadata; `None` leaves a field unchanged
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CourseUpdate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub duration: Option<u64>,
+    pub price: Option<u64>,
+    pub prerequisites: Option<Vec<String>>,
+    pub learning_objectives: Option<Vec<String>>,
+    pub syllabus: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub language: Option<String>,
+    pub certificate_enabled: Option<bool>,
+    pub max_students: Option<u64>,
 }
 
 #[contracttype]
@@ -74,9 +259,28 @@ pub enum CourseMetadataKey {
     InstructorCount,
     Completion(String),
     CompletionCount,
+    StudentCompletions(Address),
+    StudentCredits(Address, CreditSystem),
+    CredentialContract,
+    EventLoggerContract,
+    Curriculum(String),
+    Lesson(String, u32),
+    RequiredLessons(String),
+    Cohort(String, u32),
+    CohortCount(String),
+    LearnerCohort(String, Address),
+    AnalyticsContract,
+    ProgressContract,
+    Gradebook(String),
+    Grader(String, Address),
+    Score(String, Address, u32),
+    ScoresRecorded(String), // set once a course has any score; the gradebook is then fixed
     Admin,
 }
 
+/// Lowercase hex digits used to encode verification hashes
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
 #[contract]
 pub struct CourseMetadataContract;
 
@@ -95,57 +299,46 @@ impl CourseMetadataContract {
     }
 
     /// Create and store course metadata
-    pub fn create_course(
-        env: Env,
-        instructor: Address,
-        title: String,
-        description: String,
-        category: String,
-        level: String,
-        duration: u64,
-        price: u64,
-        prerequisites: Vec<String>,
-        learning_objectives: Vec<String>,
-        syllabus: String,
-        thumbnail_url: String,
-        tags: Vec<String>,
-        language: String,
-        certificate_enabled: bool,
-        max_students: u64,
-    ) -> String {
+    pub fn create_course(env: Env, instructor: Address, course: NewCourse) -> String {
         // Check if instructor exists, create if not
         if !env.storage().instance().has(&CourseMetadataKey::Instructor(instructor.clone())) {
-            Self::create_instructor_profile(env, instructor.clone());
+            Self::create_instructor_profile(env.clone(), instructor.clone());
         }
 
         let course_count: u64 = env.storage().instance()
             .get(&CourseMetadataKey::CourseCount)
             .unwrap_or(0);
         
-        let course_id = format!("course_{}", course_count + 1);
+        let course_id = numbered_id(&env, "course_", course_count + 1);
         let timestamp = env.ledger().timestamp();
         
         // Create verification hash
-        let verification_data = format!("{}{}{}{}{}", title, description, instructor, price, timestamp);
-        let verification_hash = Self::generate_hash(env, verification_data);
+        let verification_hash = Self::generate_hash(
+            env.clone(),
+            course.title.clone(),
+            course.description.clone(),
+            instructor.clone(),
+            course.price,
+            timestamp,
+        );
 
         let course_metadata = CourseMetadata {
             id: course_id.clone(),
             instructor: instructor.clone(),
-            title,
-            description,
-            category,
-            level,
-            duration,
-            price,
-            prerequisites,
-            learning_objectives,
-            syllabus,
-            thumbnail_url,
-            tags,
-            language,
-            certificate_enabled,
-            max_students,
+            title: course.title,
+            description: course.description,
+            category: course.category,
+            level: course.level,
+            duration: course.duration,
+            price: course.price,
+            prerequisites: course.prerequisites,
+            learning_objectives: course.learning_objectives,
+            syllabus: course.syllabus,
+            thumbnail_url: course.thumbnail_url,
+            tags: course.tags,
+            language: course.language,
+            certificate_enabled: course.certificate_enabled,
+            max_students: course.max_students,
             current_enrollments: 0,
             rating: 0,
             review_count: 0,
@@ -153,13 +346,20 @@ impl CourseMetadataContract {
             created_at: timestamp,
             updated_at: timestamp,
             verification_hash,
+            credit_value: 0,
+            credit_system: CreditSystem::None,
+            lesson_count: 0,
         };
 
         env.storage().instance().set(&CourseMetadataKey::Course(course_id.clone()), &course_metadata);
         env.storage().instance().set(&CourseMetadataKey::CourseCount, &(course_count + 1));
 
+        if let Some(analytics) = env.storage().instance().get::<_, Address>(&CourseMetadataKey::AnalyticsContract) {
+            AnalyticsContractClient::new(&env, &analytics).on_course_created();
+        }
+
         // Update instructor course count
-        let mut instructor_profile = Self::get_instructor_profile(env, instructor.clone());
+        let mut instructor_profile = Self::get_instructor_profile(env.clone(), instructor.clone());
         instructor_profile.course_count += 1;
         env.storage().instance().set(&CourseMetadataKey::Instructor(instructor), &instructor_profile);
 
@@ -171,20 +371,7 @@ impl CourseMetadataContract {
         env: Env,
         course_id: String,
         instructor: Address,
-        title: Option<String>,
-        description: Option<String>,
-        category: Option<String>,
-        level: Option<String>,
-        duration: Option<u64>,
-        price: Option<u64>,
-        prerequisites: Option<Vec<String>>,
-        learning_objectives: Option<Vec<String>>,
-        syllabus: Option<String>,
-        thumbnail_url: Option<String>,
-        tags: Option<Vec<String>>,
-        language: Option<String>,
-        certificate_enabled: Option<bool>,
-        max_students: Option<u64>,
+        changes: CourseUpdate,
         status: Option<CourseStatus>,
     ) -> bool {
         let mut course_metadata: CourseMetadata = env.storage().instance()
@@ -197,46 +384,46 @@ impl CourseMetadataContract {
         }
 
         // Update fields if provided
-        if let Some(new_title) = title {
+        if let Some(new_title) = changes.title {
             course_metadata.title = new_title;
         }
-        if let Some(new_description) = description {
+        if let Some(new_description) = changes.description {
             course_metadata.description = new_description;
         }
-        if let Some(new_category) = category {
+        if let Some(new_category) = changes.category {
             course_metadata.category = new_category;
         }
-        if let Some(new_level) = level {
+        if let Some(new_level) = changes.level {
             course_metadata.level = new_level;
         }
-        if let Some(new_duration) = duration {
+        if let Some(new_duration) = changes.duration {
             course_metadata.duration = new_duration;
         }
-        if let Some(new_price) = price {
+        if let Some(new_price) = changes.price {
             course_metadata.price = new_price;
         }
-        if let Some(new_prerequisites) = prerequisites {
+        if let Some(new_prerequisites) = changes.prerequisites {
             course_metadata.prerequisites = new_prerequisites;
         }
-        if let Some(new_learning_objectives) = learning_objectives {
+        if let Some(new_learning_objectives) = changes.learning_objectives {
             course_metadata.learning_objectives = new_learning_objectives;
         }
-        if let Some(new_syllabus) = syllabus {
+        if let Some(new_syllabus) = changes.syllabus {
             course_metadata.syllabus = new_syllabus;
         }
-        if let Some(new_thumbnail_url) = thumbnail_url {
+        if let Some(new_thumbnail_url) = changes.thumbnail_url {
             course_metadata.thumbnail_url = new_thumbnail_url;
         }
-        if let Some(new_tags) = tags {
+        if let Some(new_tags) = changes.tags {
             course_metadata.tags = new_tags;
         }
-        if let Some(new_language) = language {
+        if let Some(new_language) = changes.language {
             course_metadata.language = new_language;
         }
-        if let Some(new_certificate_enabled) = certificate_enabled {
+        if let Some(new_certificate_enabled) = changes.certificate_enabled {
             course_metadata.certificate_enabled = new_certificate_enabled;
         }
-        if let Some(new_max_students) = max_students {
+        if let Some(new_max_students) = changes.max_students {
             course_metadata.max_students = new_max_students;
         }
         if let Some(new_status) = status {
@@ -246,14 +433,14 @@ impl CourseMetadataContract {
         course_metadata.updated_at = env.ledger().timestamp();
 
         // Update verification hash
-        let verification_data = format!("{}{}{}{}{}", 
-            course_metadata.title, 
-            course_metadata.description, 
-            course_metadata.instructor, 
-            course_metadata.price, 
-            course_metadata.updated_at
+        course_metadata.verification_hash = Self::generate_hash(
+            env.clone(),
+            course_metadata.title.clone(),
+            course_metadata.description.clone(),
+            course_metadata.instructor.clone(),
+            course_metadata.price,
+            course_metadata.updated_at,
         );
-        course_metadata.verification_hash = Self::generate_hash(env, verification_data);
 
         env.storage().instance().set(&CourseMetadataKey::Course(course_id), &course_metadata);
         true
@@ -266,14 +453,14 @@ impl CourseMetadataContract {
             .unwrap_or_else(|| panic!("Course not found"));
 
         // Recreate verification hash and compare
-        let verification_data = format!("{}{}{}{}{}", 
-            course_metadata.title, 
-            course_metadata.description, 
-            course_metadata.instructor, 
-            course_metadata.price, 
-            course_metadata.updated_at
+        let expected_hash = Self::generate_hash(
+            env,
+            course_metadata.title.clone(),
+            course_metadata.description.clone(),
+            course_metadata.instructor.clone(),
+            course_metadata.price,
+            course_metadata.updated_at,
         );
-        let expected_hash = Self::generate_hash(env, verification_data);
 
         expected_hash == course_metadata.verification_hash
     }
@@ -311,6 +498,9 @@ impl CourseMetadataContract {
     }
 
     /// Record course completion
+    ///
+    /// The grade is the counted attempt's grade when the progress contract has one, else the gradebook's computed grade,
+    /// else `final_grade`
     pub fn record_completion(
         env: Env,
         course_id: String,
@@ -328,7 +518,39 @@ impl CourseMetadataContract {
             .get(&CourseMetadataKey::CompletionCount)
             .unwrap_or(0);
 
-        let completion_id = format!("completion_{}", completion_count + 1);
+        let gradebook = Self::get_gradebook(env.clone(), course_id.clone());
+        let attempt_grade = env.storage().instance()
+            .get::<_, Address>(&CourseMetadataKey::ProgressContract)
+            .and_then(|progress| {
+                CourseProgressContractClient::new(&env, &progress).get_counted_attempt(&student, &course_id).grade
+            });
+        let final_grade = match (attempt_grade, &gradebook) {
+            (Some(grade), _) => grade,
+            (None, Some(_)) => Self::compute_grade(env.clone(), course_id.clone(), student.clone()),
+            (None, None) => final_grade,
+        };
+        // Cohort learners are held to the cohort end date
+        let cohort = Self::get_learner_cohort(env.clone(), course_id.clone(), student.clone());
+        let deadline_status = cohort.as_ref()
+            .map(|cohort| cohort.deadline_status(None, env.ledger().timestamp()))
+            .unwrap_or(DeadlineStatus::SelfPaced);
+        let final_grade = match &deadline_status {
+            DeadlineStatus::PastCutoff => panic!("Submission is past the cohort cutoff"),
+            DeadlineStatus::Late => {
+                let penalty_percent = cohort.as_ref().unwrap().late_policy.penalty_percent;
+                final_grade - final_grade * penalty_percent / 100
+            }
+            _ => final_grade,
+        };
+
+        // The pass mark applies to the grade after any late penalty
+        if let Some(gradebook) = &gradebook {
+            if final_grade < gradebook.pass_mark {
+                panic!("Grade below pass mark");
+            }
+        }
+
+        let completion_id = numbered_id(&env, "completion_", completion_count + 1);
         let completion = CourseCompletion {
             id: completion_id.clone(),
             course_id: course_id.clone(),
@@ -338,36 +560,184 @@ impl CourseMetadataContract {
             certificate_hash,
             is_verified: false,
             skills_acquired,
+            credits: course_metadata.credit_value,
+            credit_system: course_metadata.credit_system.clone(),
+            credential_id: None,
+            cohort_id: cohort.map(|cohort| cohort.id),
+            deadline_status,
         };
 
         env.storage().instance().set(&CourseMetadataKey::Completion(completion_id.clone()), &completion);
         env.storage().instance().set(&CourseMetadataKey::CompletionCount, &(completion_count + 1));
 
+        // Index completion under the student
+        let mut student_completions: Vec<String> = env.storage().persistent()
+            .get(&CourseMetadataKey::StudentCompletions(student.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        student_completions.push_back(completion_id.clone());
+        env.storage().persistent().set(&CourseMetadataKey::StudentCompletions(student.clone()), &student_completions);
+
         // Update course enrollment count
         let mut updated_course = course_metadata;
         updated_course.current_enrollments += 1;
         env.storage().instance().set(&CourseMetadataKey::Course(course_id), &updated_course);
 
         // Update instructor total students
-        let mut instructor_profile = Self::get_instructor_profile(env, updated_course.instructor);
+        let mut instructor_profile = Self::get_instructor_profile(env.clone(), updated_course.instructor.clone());
         instructor_profile.total_students += 1;
         env.storage().instance().set(&CourseMetadataKey::Instructor(updated_course.instructor), &instructor_profile);
 
         completion_id
     }
 
-    /// Verify course completion
-    pub fn verify_completion(env: Env, completion_id: String) -> bool {
+    /// Verify course completion (Admin or course instructor)
+    ///
+    /// Certificate-enabled courses get their credential issued automatically; when the credential contract refuses,
+    /// e.g. because a proctored session is not cleared yet, the completion stays verified and the credential can be
+    /// claimed later with `claim_completion_credential`
+    pub fn verify_completion(env: Env, completion_id: String, verifier: Address) -> bool {
+        verifier.require_auth();
+
         let mut completion: CourseCompletion = env.storage().instance()
             .get(&CourseMetadataKey::Completion(completion_id.clone()))
             .unwrap_or_else(|| panic!("Completion record not found"));
 
+        let course_metadata = Self::get_course(env.clone(), completion.course_id.clone());
+        let admin: Address = env.storage().instance()
+            .get(&CourseMetadataKey::Admin)
+            .unwrap_or_else(|| panic!("Admin not found"));
+        if verifier != admin && verifier != course_metadata.instructor {
+            panic!("Only admin or course instructor can verify completions");
+        }
+
+        if completion.is_verified {
+            panic!("Completion already verified");
+        }
         completion.is_verified = true;
+
+        // Credits count towards the student's totals only once the completion is verified
+        if completion.credit_system != CreditSystem::None {
+            let credits_key = CourseMetadataKey::StudentCredits(completion.student.clone(), completion.credit_system.clone());
+            let total_credits: u64 = env.storage().persistent().get(&credits_key).unwrap_or(0);
+            env.storage().persistent().set(&credits_key, &(total_credits + completion.credits as u64));
+        }
+
+        if let Some(analytics) = env.storage().instance().get::<_, Address>(&CourseMetadataKey::AnalyticsContract) {
+            AnalyticsContractClient::new(&env, &analytics).on_course_completed(
+                &completion.student,
+                &completion.course_id,
+                &course_metadata.instructor,
+                &course_metadata.category,
+            );
+        }
+
+        if course_metadata.certificate_enabled && completion.credential_id.is_none() {
+            if let Some(credential_contract) = env.storage().instance().get::<_, Address>(&CourseMetadataKey::CredentialContract) {
+                let issued = StarkEdContractClient::new(&env, &credential_contract).try_issue_for_completion(
+                    &completion_id,
+                    &completion.student,
+                    &completion.course_id,
+                    &course_metadata.title,
+                    &course_metadata.description,
+                    &completion.certificate_hash,
+                );
+                if let Ok(Ok(credential_id)) = issued {
+                    completion.credential_id = Some(credential_id);
+                    Self::log_credential_issuance(env.clone(), &completion, completion_id.clone(), credential_id);
+                }
+            }
+        }
+
         env.storage().instance().set(&CourseMetadataKey::Completion(completion_id), &completion);
 
         true
     }
 
+    /// Issue the credential for a verified completion whose issuance was deferred
+    pub fn claim_completion_credential(env: Env, completion_id: String) -> u64 {
+        let mut completion = Self::get_completion(env.clone(), completion_id.clone());
+        if !completion.is_verified {
+            panic!("Completion is not verified");
+        }
+        if completion.credential_id.is_some() {
+            panic!("Credential already issued for completion");
+        }
+
+        let course_metadata = Self::get_course(env.clone(), completion.course_id.clone());
+        if !course_metadata.certificate_enabled {
+            panic!("Course does not issue certificates");
+        }
+        let credential_contract: Address = env.storage().instance()
+            .get(&CourseMetadataKey::CredentialContract)
+            .unwrap_or_else(|| panic!("Credential contract not set"));
+
+        let credential_id = StarkEdContractClient::new(&env, &credential_contract).issue_for_completion(
+            &completion_id,
+            &completion.student,
+            &completion.course_id,
+            &course_metadata.title,
+            &course_metadata.description,
+            &completion.certificate_hash,
+        );
+        completion.credential_id = Some(credential_id);
+        Self::log_credential_issuance(env.clone(), &completion, completion_id.clone(), credential_id);
+        env.storage().instance().set(&CourseMetadataKey::Completion(completion_id), &completion);
+
+        credential_id
+    }
+
+    /// Point a completion at the credential that now stands for it, e.g. after a dispute reissue (credential contract only)
+    pub fn set_completion_credential(env: Env, completion_id: String, credential_id: u64) {
+        let credential_contract: Address = env.storage().instance()
+            .get(&CourseMetadataKey::CredentialContract)
+            .unwrap_or_else(|| panic!("Credential contract not set"));
+        credential_contract.require_auth();
+
+        let mut completion = Self::get_completion(env.clone(), completion_id.clone());
+        completion.credential_id = Some(credential_id);
+        env.storage().instance().set(&CourseMetadataKey::Completion(completion_id), &completion);
+    }
+
+    /// Correct a completion's grade after a dispute (credential contract only)
+    pub fn amend_completion_grade(env: Env, completion_id: String, grade: u32) {
+        let credential_contract: Address = env.storage().instance()
+            .get(&CourseMetadataKey::CredentialContract)
+            .unwrap_or_else(|| panic!("Credential contract not set"));
+        credential_contract.require_auth();
+
+        if grade > 100 {
+            panic!("Grade must be between 0 and 100");
+        }
+
+        let mut completion = Self::get_completion(env.clone(), completion_id.clone());
+        completion.final_grade = grade;
+        env.storage().instance().set(&CourseMetadataKey::Completion(completion_id), &completion);
+    }
+
+    /// Set the StarkEdContract that issues credentials for completions (Admin only)
+    pub fn set_credential_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&CourseMetadataKey::CredentialContract, &contract);
+    }
+
+    /// Set the EventLoggerContract that records issued credentials (Admin only)
+    pub fn set_event_logger_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&CourseMetadataKey::EventLoggerContract, &contract);
+    }
+
+    /// Set the CourseProgressContract whose counted attempt grades completions (Admin only)
+    pub fn set_progress_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&CourseMetadataKey::ProgressContract, &contract);
+    }
+
+    /// Set the AnalyticsContract that counts courses and completions (Admin only)
+    pub fn set_analytics_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&CourseMetadataKey::AnalyticsContract, &contract);
+    }
+
     /// Get course completion record
     pub fn get_completion(env: Env, completion_id: String) -> CourseCompletion {
         env.storage().instance()
@@ -377,13 +747,473 @@ impl CourseMetadataContract {
 
     /// Get student's course completions
     pub fn get_student_completions(env: Env, student: Address) -> Vec<String> {
-        // This is a simplified implementation
-        // In production, you'd maintain an index of student completions
-        Vec::new(&env)
+        env.storage().persistent()
+            .get(&CourseMetadataKey::StudentCompletions(student))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set the credit value and credit system awarded by a course
+    pub fn set_course_credits(
+        env: Env,
+        course_id: String,
+        instructor: Address,
+        credit_value: u32,
+        credit_system: CreditSystem,
+    ) -> bool {
+        let mut course_metadata = Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+
+        if credit_system == CreditSystem::None && credit_value != 0 {
+            panic!("Credit value requires a credit system");
+        }
+
+        course_metadata.credit_value = credit_value;
+        course_metadata.credit_system = credit_system;
+        course_metadata.updated_at = env.ledger().timestamp();
+
+        env.storage().instance().set(&CourseMetadataKey::Course(course_id), &course_metadata);
+        true
+    }
+
+    /// Set the number of lessons in a course, used to validate learner progress
+    pub fn set_course_lessons(env: Env, course_id: String, instructor: Address, lesson_count: u32) -> bool {
+        let mut course_metadata = Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+
+        if !Self::get_curriculum(env.clone(), course_id.clone()).is_empty() {
+            panic!("Lesson count is managed by the curriculum");
+        }
+
+        course_metadata.lesson_count = lesson_count;
+        course_metadata.updated_at = env.ledger().timestamp();
+
+        env.storage().instance().set(&CourseMetadataKey::Course(course_id), &course_metadata);
+        true
+    }
+
+    /// Append a module to a course's curriculum
+    pub fn add_module(env: Env, course_id: String, instructor: Address, title: String) -> u32 {
+        let course_metadata = Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+
+        let mut curriculum = Self::get_curriculum(env.clone(), course_id.clone());
+        if curriculum.is_empty() && course_metadata.lesson_count != 0 {
+            panic!("Lesson count was set without a curriculum");
+        }
+
+        let module_id = curriculum.len();
+        curriculum.push_back(CurriculumModule {
+            id: module_id,
+            title,
+            lesson_ids: Vec::new(&env),
+        });
+
+        env.storage().persistent().set(&CourseMetadataKey::Curriculum(course_id), &curriculum);
+        module_id
+    }
+
+    /// Append a lesson to a curriculum module
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_lesson(
+        env: Env,
+        course_id: String,
+        instructor: Address,
+        module_id: u32,
+        title: String,
+        content_cid: String,
+        is_required: bool,
+        estimated_minutes: u32,
+    ) -> u32 {
+        let mut course_metadata = Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+
+        // Modules are looked up by id; reorder_modules changes their positions
+        let mut curriculum = Self::get_curriculum(env.clone(), course_id.clone());
+        let position = curriculum.iter()
+            .position(|module| module.id == module_id)
+            .unwrap_or_else(|| panic!("Module not found")) as u32;
+        let mut module = curriculum.get(position).unwrap();
+
+        let lesson_id = course_metadata.lesson_count;
+        let lesson = Lesson {
+            id: lesson_id,
+            module_id,
+            title,
+            content_cid,
+            is_required,
+            estimated_minutes,
+        };
+
+        module.lesson_ids.push_back(lesson_id);
+        curriculum.set(position, module);
+
+        if is_required {
+            let mut required_lessons = Self::get_required_lessons(env.clone(), course_id.clone());
+            required_lessons.push_back(lesson_id);
+            env.storage().persistent().set(&CourseMetadataKey::RequiredLessons(course_id.clone()), &required_lessons);
+        }
+
+        course_metadata.lesson_count += 1;
+        course_metadata.updated_at = env.ledger().timestamp();
+
+        env.storage().persistent().set(&CourseMetadataKey::Lesson(course_id.clone(), lesson_id), &lesson);
+        env.storage().persistent().set(&CourseMetadataKey::Curriculum(course_id.clone()), &curriculum);
+        env.storage().instance().set(&CourseMetadataKey::Course(course_id), &course_metadata);
+
+        lesson_id
+    }
+
+    /// Update a lesson's content and flags; lessons cannot be removed once added
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_lesson(
+        env: Env,
+        course_id: String,
+        instructor: Address,
+        lesson_id: u32,
+        title: Option<String>,
+        content_cid: Option<String>,
+        is_required: Option<bool>,
+        estimated_minutes: Option<u32>,
+    ) -> bool {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+        let mut lesson = Self::get_lesson(env.clone(), course_id.clone(), lesson_id);
+
+        if let Some(new_title) = title {
+            lesson.title = new_title;
+        }
+        if let Some(new_content_cid) = content_cid {
+            lesson.content_cid = new_content_cid;
+        }
+        if let Some(new_estimated_minutes) = estimated_minutes {
+            lesson.estimated_minutes = new_estimated_minutes;
+        }
+        if let Some(new_is_required) = is_required {
+            if new_is_required != lesson.is_required {
+                let mut required_lessons = Self::get_required_lessons(env.clone(), course_id.clone());
+                if new_is_required {
+                    required_lessons.push_back(lesson_id);
+                } else if let Some(index) = required_lessons.first_index_of(lesson_id) {
+                    required_lessons.remove(index);
+                }
+                env.storage().persistent().set(&CourseMetadataKey::RequiredLessons(course_id.clone()), &required_lessons);
+            }
+            lesson.is_required = new_is_required;
+        }
+
+        env.storage().persistent().set(&CourseMetadataKey::Lesson(course_id, lesson_id), &lesson);
+        true
+    }
+
+    /// Reorder a course's modules; `module_ids` must list every module exactly once
+    pub fn reorder_modules(env: Env, course_id: String, instructor: Address, module_ids: Vec<u32>) -> bool {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+        let curriculum = Self::get_curriculum(env.clone(), course_id.clone());
+
+        if module_ids.len() != curriculum.len() {
+            panic!("Module order must list every module");
+        }
+
+        let mut reordered = Vec::new(&env);
+        for module_id in module_ids.iter() {
+            let module = curriculum.iter()
+                .find(|module| module.id == module_id)
+                .unwrap_or_else(|| panic!("Module not found"));
+            if reordered.iter().any(|existing: CurriculumModule| existing.id == module_id) {
+                panic!("Module order must list every module");
+            }
+            reordered.push_back(module);
+        }
+
+        env.storage().persistent().set(&CourseMetadataKey::Curriculum(course_id), &reordered);
+        true
+    }
+
+    /// Get a course's curriculum modules in teaching order
+    pub fn get_curriculum(env: Env, course_id: String) -> Vec<CurriculumModule> {
+        env.storage().persistent()
+            .get(&CourseMetadataKey::Curriculum(course_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get a lesson of a course's curriculum
+    pub fn get_lesson(env: Env, course_id: String, lesson_id: u32) -> Lesson {
+        env.storage().persistent()
+            .get(&CourseMetadataKey::Lesson(course_id, lesson_id))
+            .unwrap_or_else(|| panic!("Lesson not found"))
+    }
+
+    /// Get the ids of the lessons required to complete a course
+    pub fn get_required_lessons(env: Env, course_id: String) -> Vec<u32> {
+        env.storage().persistent()
+            .get(&CourseMetadataKey::RequiredLessons(course_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Open a cohort of a course with fixed dates and a late-submission policy
+    pub fn create_cohort(
+        env: Env,
+        course_id: String,
+        instructor: Address,
+        start_date: u64,
+        end_date: u64,
+        late_policy: LatePolicy,
+    ) -> u32 {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+
+        if end_date <= start_date {
+            panic!("Cohort must end after it starts");
+        }
+        if late_policy.penalty_percent > 100 {
+            panic!("Penalty must be 0-100");
+        }
+        if let Some(cutoff) = late_policy.hard_cutoff {
+            if cutoff < late_policy.grace_period {
+                panic!("Cutoff cannot fall inside the grace period");
+            }
+        }
+
+        let cohort_id: u32 = env.storage().persistent()
+            .get(&CourseMetadataKey::CohortCount(course_id.clone()))
+            .unwrap_or(0);
+        let cohort = Cohort {
+            id: cohort_id,
+            course_id: course_id.clone(),
+            start_date,
+            end_date,
+            due_dates: Vec::new(&env),
+            late_policy,
+            learner_count: 0,
+        };
+
+        env.storage().persistent().set(&CourseMetadataKey::Cohort(course_id.clone(), cohort_id), &cohort);
+        env.storage().persistent().set(&CourseMetadataKey::CohortCount(course_id), &(cohort_id + 1));
+
+        cohort_id
+    }
+
+    /// Set or move the due date of a lesson within a cohort
+    pub fn set_lesson_due_date(
+        env: Env,
+        course_id: String,
+        instructor: Address,
+        cohort_id: u32,
+        lesson_id: u32,
+        due_at: u64,
+    ) -> bool {
+        let course_metadata = Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+        let mut cohort = Self::get_cohort(env.clone(), course_id.clone(), cohort_id);
+
+        if lesson_id >= course_metadata.lesson_count {
+            panic!("Lesson not found");
+        }
+        if due_at < cohort.start_date || due_at > cohort.end_date {
+            panic!("Due date must fall within the cohort");
+        }
+
+        let due_date = LessonDueDate { lesson_id, due_at };
+        match cohort.due_dates.iter().position(|existing| existing.lesson_id == lesson_id) {
+            Some(index) => cohort.due_dates.set(index as u32, due_date),
+            None => cohort.due_dates.push_back(due_date),
+        }
+
+        env.storage().persistent().set(&CourseMetadataKey::Cohort(course_id, cohort_id), &cohort);
+        true
+    }
+
+    /// Place a learner in a cohort; a learner belongs to at most one cohort per course
+    pub fn enroll_in_cohort(env: Env, course_id: String, instructor: Address, cohort_id: u32, student: Address) -> bool {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+        let mut cohort = Self::get_cohort(env.clone(), course_id.clone(), cohort_id);
+
+        let learner_key = CourseMetadataKey::LearnerCohort(course_id.clone(), student);
+        if env.storage().persistent().has(&learner_key) {
+            panic!("Learner already in a cohort");
+        }
+        if env.ledger().timestamp() > cohort.end_date {
+            panic!("Cohort has ended");
+        }
+
+        cohort.learner_count += 1;
+        env.storage().persistent().set(&learner_key, &cohort_id);
+        env.storage().persistent().set(&CourseMetadataKey::Cohort(course_id, cohort_id), &cohort);
+        true
+    }
+
+    /// Get a cohort of a course
+    pub fn get_cohort(env: Env, course_id: String, cohort_id: u32) -> Cohort {
+        env.storage().persistent()
+            .get(&CourseMetadataKey::Cohort(course_id, cohort_id))
+            .unwrap_or_else(|| panic!("Cohort not found"))
+    }
+
+    /// Get the cohort a learner is enrolled in, if the learner is not self-paced
+    pub fn get_learner_cohort(env: Env, course_id: String, student: Address) -> Option<Cohort> {
+        let cohort_id: Option<u32> = env.storage().persistent()
+            .get(&CourseMetadataKey::LearnerCohort(course_id.clone(), student));
+        cohort_id.map(|cohort_id| Self::get_cohort(env, course_id, cohort_id))
+    }
+
+    /// Set a course's weighted grade components, rounding and pass mark; fixed once any score is recorded
+    pub fn set_gradebook(
+        env: Env,
+        course_id: String,
+        instructor: Address,
+        components: Vec<GradeComponent>,
+        rounding: GradeRounding,
+        pass_mark: u32,
+    ) -> bool {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+
+        if env.storage().persistent().has(&CourseMetadataKey::ScoresRecorded(course_id.clone())) {
+            panic!("Gradebook cannot change once scores are recorded");
+        }
+        if pass_mark > 100 {
+            panic!("Pass mark must be 0-100");
+        }
+
+        let mut total_weight = 0u32;
+        for (index, component) in components.iter().enumerate() {
+            if component.id != index as u32 {
+                panic!("Component ids must be sequential");
+            }
+            if component.max_score == 0 {
+                panic!("Component max score must be positive");
+            }
+            total_weight += component.weight;
+        }
+        if total_weight != 100 {
+            panic!("Component weights must sum to 100");
+        }
+
+        let gradebook = Gradebook {
+            components,
+            rounding,
+            pass_mark,
+        };
+        env.storage().persistent().set(&CourseMetadataKey::Gradebook(course_id), &gradebook);
+        true
+    }
+
+    /// Get a course's gradebook, if it has one
+    pub fn get_gradebook(env: Env, course_id: String) -> Option<Gradebook> {
+        env.storage().persistent().get(&CourseMetadataKey::Gradebook(course_id))
+    }
+
+    /// Allow an address to enter scores for a course
+    pub fn add_grader(env: Env, course_id: String, instructor: Address, grader: Address) -> bool {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+        env.storage().persistent().set(&CourseMetadataKey::Grader(course_id, grader), &true);
+        true
+    }
+
+    /// Revoke an address's permission to enter scores for a course
+    pub fn remove_grader(env: Env, course_id: String, instructor: Address, grader: Address) -> bool {
+        Self::load_course_for_instructor(env.clone(), course_id.clone(), instructor);
+        env.storage().persistent().remove(&CourseMetadataKey::Grader(course_id, grader));
+        true
+    }
+
+    /// Check whether an address may enter scores for a course
+    pub fn is_grader(env: Env, course_id: String, grader: Address) -> bool {
+        env.storage().persistent()
+            .get(&CourseMetadataKey::Grader(course_id, grader))
+            .unwrap_or(false)
+    }
+
+    /// Enter or correct a student's score for a grade component (course instructor or grader)
+    pub fn record_score(
+        env: Env,
+        course_id: String,
+        grader: Address,
+        student: Address,
+        component_id: u32,
+        score: u32,
+    ) -> bool {
+        grader.require_auth();
+
+        let course_metadata = Self::get_course(env.clone(), course_id.clone());
+        if course_metadata.instructor != grader && !Self::is_grader(env.clone(), course_id.clone(), grader.clone()) {
+            panic!("Only course instructor or grader can record scores");
+        }
+
+        let gradebook = Self::get_gradebook(env.clone(), course_id.clone())
+            .unwrap_or_else(|| panic!("Gradebook not found"));
+        let component = gradebook.components.get(component_id)
+            .unwrap_or_else(|| panic!("Grade component not found"));
+        if score > component.max_score {
+            panic!("Score exceeds component max score");
+        }
+
+        let entry = ScoreEntry {
+            score,
+            graded_by: grader,
+            graded_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&CourseMetadataKey::Score(course_id.clone(), student, component_id), &entry);
+        env.storage().persistent().set(&CourseMetadataKey::ScoresRecorded(course_id), &true);
+        true
+    }
+
+    /// Get a student's score for a grade component
+    pub fn get_score(env: Env, course_id: String, student: Address, component_id: u32) -> Option<ScoreEntry> {
+        env.storage().persistent().get(&CourseMetadataKey::Score(course_id, student, component_id))
+    }
+
+    /// Compute a student's weighted grade (0-100); components without a score count as zero
+    pub fn compute_grade(env: Env, course_id: String, student: Address) -> u32 {
+        let gradebook = Self::get_gradebook(env.clone(), course_id.clone())
+            .unwrap_or_else(|| panic!("Gradebook not found"));
+
+        // Ten-thousandths of a grade point
+        let mut weighted_total = 0u64;
+        for component in gradebook.components.iter() {
+            if let Some(entry) = Self::get_score(env.clone(), course_id.clone(), student.clone(), component.id) {
+                weighted_total += component.weight as u64 * entry.score as u64 * 10000 / component.max_score as u64;
+            }
+        }
+
+        let grade = match gradebook.rounding {
+            GradeRounding::Down => weighted_total / 10000,
+            GradeRounding::Nearest => (weighted_total + 5000) / 10000,
+            GradeRounding::Up => weighted_total.div_ceil(10000),
+        };
+        grade as u32
+    }
+
+    /// Get a student's total credits earned in a credit system from verified completions
+    pub fn get_student_credits(env: Env, student: Address, credit_system: CreditSystem) -> u64 {
+        env.storage().persistent()
+            .get(&CourseMetadataKey::StudentCredits(student, credit_system))
+            .unwrap_or(0)
+    }
+
+    /// Get credits earned from verified completions in a credit system since a date, optionally limited to a category
+    pub fn get_credits_earned(
+        env: Env,
+        student: Address,
+        credit_system: CreditSystem,
+        category: Option<String>,
+        since: u64,
+    ) -> u64 {
+        let mut total_credits = 0u64;
+
+        for completion_id in Self::get_student_completions(env.clone(), student).iter() {
+            let completion = Self::get_completion(env.clone(), completion_id);
+            if !completion.is_verified || completion.credit_system != credit_system || completion.completion_date < since {
+                continue;
+            }
+
+            if let Some(category) = &category {
+                let course = Self::get_course(env.clone(), completion.course_id);
+                if course.category != *category {
+                    continue;
+                }
+            }
+
+            total_credits += completion.credits as u64;
+        }
+
+        total_credits
     }
 
     /// Get instructor's courses
-    pub fn get_instructor_courses(env: Env, instructor: Address) -> Vec<String> {
+    pub fn get_instructor_courses(env: Env, _instructor: Address) -> Vec<String> {
         // This is a simplified implementation
         // In production, you'd maintain an index of instructor courses
         Vec::new(&env)
@@ -403,19 +1233,61 @@ impl CourseMetadataContract {
             .unwrap_or(0)
     }
 
-    /// Generate simple hash (in production, use proper cryptographic hash)
-    fn generate_hash(env: Env, data: String) -> String {
-        // Simple hash implementation for demonstration
-        // In production, use SHA-256 or similar
-        let mut hash = 0u64;
-        for byte in data.into_bytes() {
-            hash = hash.wrapping_mul(31).wrapping_add(byte as u64);
+    /// Load a course and require authorization from its instructor
+    fn load_course_for_instructor(env: Env, course_id: String, instructor: Address) -> CourseMetadata {
+        let course_metadata = Self::get_course(env, course_id);
+
+        // Verify instructor ownership
+        if course_metadata.instructor != instructor {
+            panic!("Only course instructor can update course");
+        }
+        instructor.require_auth();
+
+        course_metadata
+    }
+
+    /// Record an issued completion credential with the event logger, when one is set
+    fn log_credential_issuance(env: Env, completion: &CourseCompletion, completion_id: String, credential_id: u64) {
+        if let Some(event_logger) = env.storage().instance().get::<_, Address>(&CourseMetadataKey::EventLoggerContract) {
+            EventLoggerContractClient::new(&env, &event_logger).log_credential_issuance(
+                &env.current_contract_address(),
+                &completion.student,
+                &credential_id,
+                &completion.course_id,
+                &completion_id,
+            );
+        }
+    }
+
+    /// Require authorization from the admin address
+    fn require_admin(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&CourseMetadataKey::Admin)
+            .unwrap_or_else(|| panic!("Admin not found"));
+
+        if caller != admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+
+    /// Hex-encoded SHA-256 over the XDR of the fields a course's verification hash covers
+    fn generate_hash(env: Env, title: String, description: String, instructor: Address, price: u64, timestamp: u64) -> String {
+        let digest: BytesN<32> = env.crypto()
+            .sha256(&(title, description, instructor, price, timestamp).to_xdr(&env))
+            .into();
+
+        let mut hex = [0u8; 64];
+        for (i, byte) in digest.to_array().iter().enumerate() {
+            hex[i * 2] = HEX_DIGITS[(byte >> 4) as usize];
+            hex[i * 2 + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
         }
-        format!("{:x}", hash)
+        String::from_bytes(&env, &hex)
     }
 
     /// Rate a course
-    pub fn rate_course(env: Env, course_id: String, rater: Address, rating: u32) -> bool {
+    pub fn rate_course(env: Env, course_id: String, _rater: Address, rating: u32) -> bool {
         if rating > 100 {
             panic!("Rating must be between 0 and 100");
         }
@@ -426,7 +1298,7 @@ impl CourseMetadataContract {
 
         // Simple rating calculation (in production, store individual ratings)
         course_metadata.review_count += 1;
-        course_metadata.rating = ((course_metadata.rating * (course_metadata.review_count - 1) + rating) / course_metadata.review_count) as u32;
+        course_metadata.rating = ((course_metadata.rating as u64 * (course_metadata.review_count - 1) + rating as u64) / course_metadata.review_count) as u32;
 
         env.storage().instance().set(&CourseMetadataKey::Course(course_id), &course_metadata);
         true
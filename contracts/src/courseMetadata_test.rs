@@ -1,62 +1,70 @@
 #![cfg(test)]
 
-use soroban_sdk::{vec, Address, Env, String, Vec};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
 use crate::courseMetadata::{
-    CourseMetadataContract, CourseMetadata, CourseStatus, CourseCompletion, InstructorProfile, CourseMetadataKey
+    CourseMetadataContract, CourseMetadataContractClient, CourseStatus, CourseMetadataKey,
+    CourseUpdate, CreditSystem, GradeComponent, GradeComponentKind, GradeRounding, NewCourse
 };
 
+/// Register the contract with all authorizations mocked
+fn setup(env: &Env) -> CourseMetadataContractClient<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, CourseMetadataContract);
+    CourseMetadataContractClient::new(env, &contract_id)
+}
+
 #[test]
 fn test_initialize() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
 
     // Test successful initialization
-    CourseMetadataContract::initialize(env.clone(), admin.clone());
+    client.initialize(&admin);
     
     // Verify admin is set
-    let stored_admin: Address = env.storage().instance()
-        .get(&CourseMetadataKey::Admin)
-        .unwrap();
+    let stored_admin: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&CourseMetadataKey::Admin).unwrap()
+    });
     assert_eq!(stored_admin, admin);
 
     // Test double initialization fails
-    let result = std::panic::catch_unwind(|| {
-        CourseMetadataContract::initialize(env, admin);
-    });
-    assert!(result.is_err());
+    assert!(client.try_initialize(&admin).is_err());
 }
 
 #[test]
 fn test_create_course() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor.clone(),
-        String::from_str(&env, "Introduction to Rust"),
-        String::from_str(&env, "Learn the basics of Rust programming"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40, // 40 hours
-        1000000, // price in stroops
-        vec![&env, String::from_str(&env, "Basic programming")],
-        vec![&env, String::from_str(&env, "Understand Rust syntax"), String::from_str(&env, "Write basic Rust programs")],
-        String::from_str(&env, "QmHash123"), // IPFS hash
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env, String::from_str(&env, "rust"), String::from_str(&env, "programming")],
-        String::from_str(&env, "English"),
-        true, // certificate enabled
-        100, // max students
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Introduction to Rust"),
+            description: String::from_str(&env, "Learn the basics of Rust programming"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40, // 40 hours
+            price: 1000000, // price in stroops
+            prerequisites: vec![&env, String::from_str(&env, "Basic programming")],
+            learning_objectives: vec![&env, String::from_str(&env, "Understand Rust syntax"), String::from_str(&env, "Write basic Rust programs")],
+            syllabus: String::from_str(&env, "QmHash123"), // IPFS hash
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env, String::from_str(&env, "rust"), String::from_str(&env, "programming")],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true, // certificate enabled
+            max_students: 100, // max students
+        },
     );
 
     // Verify course was created
-    let course = CourseMetadataContract::get_course(env.clone(), course_id.clone());
+    let course = client.get_course(&course_id);
     assert_eq!(course.title, String::from_str(&env, "Introduction to Rust"));
     assert_eq!(course.instructor, instructor);
     assert_eq!(course.price, 1000000);
@@ -66,7 +74,7 @@ fn test_create_course() {
     assert!(matches!(course.status, CourseStatus::Active));
 
     // Verify instructor profile was created
-    let instructor_profile = CourseMetadataContract::get_instructor_profile(env, instructor);
+    let instructor_profile = client.get_instructor_profile(&instructor);
     assert_eq!(instructor_profile.address, instructor);
     assert_eq!(instructor_profile.course_count, 1);
     assert_eq!(instructor_profile.total_students, 0);
@@ -75,58 +83,61 @@ fn test_create_course() {
 #[test]
 fn test_update_course() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor.clone(),
-        String::from_str(&env, "Original Title"),
-        String::from_str(&env, "Original description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Original Title"),
+            description: String::from_str(&env, "Original description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Update the course
-    let update_result = CourseMetadataContract::update_course(
-        env.clone(),
-        course_id.clone(),
-        instructor.clone(),
-        Some(String::from_str(&env, "Updated Title")),
-        Some(String::from_str(&env, "Updated description")),
-        None, // category unchanged
-        Some(String::from_str(&env, "intermediate")),
-        Some(60), // updated duration
-        Some(2000000), // updated price
-        None, // prerequisites unchanged
-        None, // learning objectives unchanged
-        None, // syllabus unchanged
-        None, // thumbnail unchanged
-        None, // tags unchanged
-        None, // language unchanged
-        None, // certificate enabled unchanged
-        None, // max students unchanged
-        Some(CourseStatus::Inactive),
+    let update_result = client.update_course(
+        &course_id,
+        &instructor,
+        &CourseUpdate {
+            title: Some(String::from_str(&env, "Updated Title")),
+            description: Some(String::from_str(&env, "Updated description")),
+            category: None, // category unchanged
+            level: Some(String::from_str(&env, "intermediate")),
+            duration: Some(60), // updated duration
+            price: Some(2000000), // updated price
+            prerequisites: None, // prerequisites unchanged
+            learning_objectives: None, // learning objectives unchanged
+            syllabus: None, // syllabus unchanged
+            thumbnail_url: None, // thumbnail unchanged
+            tags: None, // tags unchanged
+            language: None, // language unchanged
+            certificate_enabled: None, // certificate enabled unchanged
+            max_students: None, // max students unchanged
+        },
+        &Some(CourseStatus::Inactive),
     );
 
     assert!(update_result);
 
     // Verify updates
-    let updated_course = CourseMetadataContract::get_course(env, course_id);
+    let updated_course = client.get_course(&course_id);
     assert_eq!(updated_course.title, String::from_str(&env, "Updated Title"));
     assert_eq!(updated_course.description, String::from_str(&env, "Updated description"));
     assert_eq!(updated_course.level, String::from_str(&env, "intermediate"));
@@ -140,88 +151,91 @@ fn test_update_course() {
 #[test]
 fn test_verify_course() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor.clone(),
-        String::from_str(&env, "Test Course"),
-        String::from_str(&env, "Test description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Verify course authenticity
-    let is_valid = CourseMetadataContract::verify_course(env.clone(), course_id.clone());
+    let is_valid = client.verify_course(&course_id);
     assert!(is_valid);
 
     // Get course and manually tamper with verification hash test
-    let mut course = CourseMetadataContract::get_course(env.clone(), course_id);
+    let mut course = client.get_course(&course_id);
     course.verification_hash = String::from_str(&env, "tampered_hash");
     
     // This would require direct storage access to test tampering
     // For now, we just verify the verification logic works
-    let original_verification = CourseMetadataContract::verify_course(env, course_id);
+    let original_verification = client.verify_course(&course_id);
     assert!(original_verification); // Should still be valid as we didn't actually tamper
 }
 
 #[test]
 fn test_record_completion() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
     let student = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor.clone(),
-        String::from_str(&env, "Test Course"),
-        String::from_str(&env, "Test description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Record course completion
-    let completion_id = CourseMetadataContract::record_completion(
-        env.clone(),
-        course_id.clone(),
-        student.clone(),
-        85, // final grade
-        String::from_str(&env, "QmCertHash456"), // certificate hash
-        vec![&env, String::from_str(&env, "Rust basics"), String::from_str(&env, "Memory management")],
+    let completion_id = client.record_completion(
+        &course_id,
+        &student,
+        &85, // final grade
+        &String::from_str(&env, "QmCertHash456"), // certificate hash
+        &vec![&env, String::from_str(&env, "Rust basics"), String::from_str(&env, "Memory management")],
     );
 
     // Verify completion was recorded
-    let completion = CourseMetadataContract::get_completion(env.clone(), completion_id.clone());
+    let completion = client.get_completion(&completion_id);
     assert_eq!(completion.course_id, course_id);
     assert_eq!(completion.student, student);
     assert_eq!(completion.final_grade, 85);
@@ -230,112 +244,115 @@ fn test_record_completion() {
     assert_eq!(completion.skills_acquired.len(), 2);
 
     // Verify course enrollment count updated
-    let updated_course = CourseMetadataContract::get_course(env.clone(), course_id);
+    let updated_course = client.get_course(&course_id);
     assert_eq!(updated_course.current_enrollments, 1);
 
     // Verify instructor student count updated
-    let updated_instructor = CourseMetadataContract::get_instructor_profile(env, instructor);
+    let updated_instructor = client.get_instructor_profile(&instructor);
     assert_eq!(updated_instructor.total_students, 1);
 }
 
 #[test]
 fn test_verify_completion() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
     let student = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor,
-        String::from_str(&env, "Test Course"),
-        String::from_str(&env, "Test description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Record completion
-    let completion_id = CourseMetadataContract::record_completion(
-        env.clone(),
-        course_id,
-        student,
-        85,
-        String::from_str(&env, "QmCertHash456"),
-        vec![&env, String::from_str(&env, "Rust basics")],
+    let completion_id = client.record_completion(
+        &course_id,
+        &student,
+        &85,
+        &String::from_str(&env, "QmCertHash456"),
+        &vec![&env, String::from_str(&env, "Rust basics")],
     );
 
     // Verify completion is initially not verified
-    let completion = CourseMetadataContract::get_completion(env.clone(), completion_id.clone());
+    let completion = client.get_completion(&completion_id);
     assert!(!completion.is_verified);
 
     // Verify completion
-    let verify_result = CourseMetadataContract::verify_completion(env.clone(), completion_id.clone());
+    let verify_result = client.verify_completion(&completion_id, &instructor);
     assert!(verify_result);
 
     // Check that completion is now verified
-    let verified_completion = CourseMetadataContract::get_completion(env, completion_id);
+    let verified_completion = client.get_completion(&completion_id);
     assert!(verified_completion.is_verified);
 }
 
 #[test]
 fn test_rate_course() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
     let rater = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor,
-        String::from_str(&env, "Test Course"),
-        String::from_str(&env, "Test description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Rate the course
-    let rate_result = CourseMetadataContract::rate_course(env.clone(), course_id.clone(), rater, 80);
+    let rate_result = client.rate_course(&course_id, &rater, &80);
     assert!(rate_result);
 
     // Check rating was updated
-    let rated_course = CourseMetadataContract::get_course(env.clone(), course_id);
+    let rated_course = client.get_course(&course_id);
     assert_eq!(rated_course.rating, 80);
     assert_eq!(rated_course.review_count, 1);
 
     // Rate again
-    let rate_result2 = CourseMetadataContract::rate_course(env.clone(), course_id.clone(), rater, 90);
+    let rate_result2 = client.rate_course(&course_id, &rater, &90);
     assert!(rate_result2);
 
     // Check average rating calculation
-    let final_course = CourseMetadataContract::get_course(env, course_id);
+    let final_course = client.get_course(&course_id);
     assert_eq!(final_course.rating, 85); // (80 + 90) / 2 = 85
     assert_eq!(final_course.review_count, 2);
 }
@@ -343,147 +360,296 @@ fn test_rate_course() {
 #[test]
 fn test_get_course_count() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Initially no courses
-    assert_eq!(CourseMetadataContract::get_course_count(env.clone()), 0);
+    assert_eq!(client.get_course_count(), 0);
 
     // Create courses
-    CourseMetadataContract::create_course(
-        env.clone(),
-        instructor.clone(),
-        String::from_str(&env, "Course 1"),
-        String::from_str(&env, "Description 1"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Course 1"),
+            description: String::from_str(&env, "Description 1"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
-    assert_eq!(CourseMetadataContract::get_course_count(env.clone()), 1);
-
-    CourseMetadataContract::create_course(
-        env.clone(),
-        instructor,
-        String::from_str(&env, "Course 2"),
-        String::from_str(&env, "Description 2"),
-        String::from_str(&env, "Design"),
-        String::from_str(&env, "intermediate"),
-        60,
-        2000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash456"),
-        String::from_str(&env, "https://example.com/thumbnail2.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        50,
+    assert_eq!(client.get_course_count(), 1);
+
+    client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Course 2"),
+            description: String::from_str(&env, "Description 2"),
+            category: String::from_str(&env, "Design"),
+            level: String::from_str(&env, "intermediate"),
+            duration: 60,
+            price: 2000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash456"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail2.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 50,
+        },
     );
 
-    assert_eq!(CourseMetadataContract::get_course_count(env), 2);
+    assert_eq!(client.get_course_count(), 2);
 }
 
 #[test]
 fn test_get_completion_count() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
     let student = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Initially no completions
-    assert_eq!(CourseMetadataContract::get_completion_count(env.clone()), 0);
+    assert_eq!(client.get_completion_count(), 0);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor,
-        String::from_str(&env, "Test Course"),
-        String::from_str(&env, "Test description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Record completions
-    CourseMetadataContract::record_completion(
-        env.clone(),
-        course_id.clone(),
-        student.clone(),
-        85,
-        String::from_str(&env, "QmCertHash456"),
-        vec![&env, String::from_str(&env, "Rust basics")],
+    client.record_completion(
+        &course_id,
+        &student,
+        &85,
+        &String::from_str(&env, "QmCertHash456"),
+        &vec![&env, String::from_str(&env, "Rust basics")],
     );
 
-    assert_eq!(CourseMetadataContract::get_completion_count(env.clone()), 1);
+    assert_eq!(client.get_completion_count(), 1);
 
-    CourseMetadataContract::record_completion(
-        env.clone(),
-        course_id,
-        student,
-        90,
-        String::from_str(&env, "QmCertHash789"),
-        vec![&env, String::from_str(&env, "Advanced Rust")],
+    client.record_completion(
+        &course_id,
+        &student,
+        &90,
+        &String::from_str(&env, "QmCertHash789"),
+        &vec![&env, String::from_str(&env, "Advanced Rust")],
     );
 
-    assert_eq!(CourseMetadataContract::get_completion_count(env), 2);
+    assert_eq!(client.get_completion_count(), 2);
 }
 
 #[test]
 #[should_panic(expected = "Rating must be between 0 and 100")]
 fn test_invalid_rating() {
     let env = Env::default();
+    let client = setup(&env);
     let admin = Address::generate(&env);
     let instructor = Address::generate(&env);
     let rater = Address::generate(&env);
 
     // Initialize contract
-    CourseMetadataContract::initialize(env.clone(), admin);
+    client.initialize(&admin);
 
     // Create a course
-    let course_id = CourseMetadataContract::create_course(
-        env.clone(),
-        instructor,
-        String::from_str(&env, "Test Course"),
-        String::from_str(&env, "Test description"),
-        String::from_str(&env, "Programming"),
-        String::from_str(&env, "beginner"),
-        40,
-        1000000,
-        vec![&env],
-        vec![&env],
-        String::from_str(&env, "QmHash123"),
-        String::from_str(&env, "https://example.com/thumbnail.jpg"),
-        vec![&env],
-        String::from_str(&env, "English"),
-        true,
-        100,
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
     );
 
     // Try to rate with invalid rating (should panic)
-    CourseMetadataContract::rate_course(env, course_id, rater, 150); // Invalid rating > 100
+    client.rate_course(&course_id, &rater, &150); // Invalid rating > 100
+}
+
+#[test]
+fn test_credit_accounting() {
+    let env = Env::default();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let instructor = Address::generate(&env);
+    let student = Address::generate(&env);
+
+    // Initialize contract
+    client.initialize(&admin);
+
+    // Create a course
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+
+    // Award 1.5 CEU
+    client.set_course_credits(
+        &course_id,
+        &instructor,
+        &150,
+        &CreditSystem::Ceu,
+    );
+
+    let completion_id = client.record_completion(
+        &course_id,
+        &student,
+        &90,
+        &String::from_str(&env, "QmCertHash456"),
+        &vec![&env],
+    );
+
+    // Verify credits carried onto the completion
+    let completion = client.get_completion(&completion_id);
+    assert_eq!(completion.credits, 150);
+    assert_eq!(completion.credit_system, CreditSystem::Ceu);
+
+    // Credits are not earned until the completion is verified
+    assert_eq!(client.get_student_credits(&student, &CreditSystem::Ceu), 0);
+    assert_eq!(client.get_credits_earned(&student, &CreditSystem::Ceu, &None, &0), 0);
+    client.verify_completion(&completion_id, &instructor);
+
+    // Verify per-system totals
+    assert_eq!(client.get_student_credits(&student, &CreditSystem::Ceu), 150);
+    assert_eq!(client.get_student_credits(&student, &CreditSystem::Ects), 0);
+
+    // Verify category and date filters
+    let programming = Some(String::from_str(&env, "Programming"));
+    let design = Some(String::from_str(&env, "Design"));
+    assert_eq!(client.get_credits_earned(&student, &CreditSystem::Ceu, &programming, &0), 150);
+    assert_eq!(client.get_credits_earned(&student, &CreditSystem::Ceu, &design, &0), 0);
+    assert_eq!(client.get_credits_earned(&student, &CreditSystem::Ceu, &None, &u64::MAX), 0);
+}
+
+#[test]
+fn test_weighted_gradebook() {
+    let env = Env::default();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let instructor = Address::generate(&env);
+    let grader = Address::generate(&env);
+    let student = Address::generate(&env);
+
+    // Initialize contract
+    client.initialize(&admin);
+
+    // Create a course
+    let course_id = client.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(&env, "Test Course"),
+            description: String::from_str(&env, "Test description"),
+            category: String::from_str(&env, "Programming"),
+            level: String::from_str(&env, "beginner"),
+            duration: 40,
+            price: 1000000,
+            prerequisites: vec![&env],
+            learning_objectives: vec![&env],
+            syllabus: String::from_str(&env, "QmHash123"),
+            thumbnail_url: String::from_str(&env, "https://example.com/thumbnail.jpg"),
+            tags: vec![&env],
+            language: String::from_str(&env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+
+    // 30% quizzes, 30% assignments, 40% final exam
+    let components = vec![
+        &env,
+        GradeComponent { id: 0, name: String::from_str(&env, "Quizzes"), kind: GradeComponentKind::Quiz, weight: 30, max_score: 20 },
+        GradeComponent { id: 1, name: String::from_str(&env, "Assignments"), kind: GradeComponentKind::Assignment, weight: 30, max_score: 100 },
+        GradeComponent { id: 2, name: String::from_str(&env, "Final exam"), kind: GradeComponentKind::Exam, weight: 40, max_score: 60 },
+    ];
+    client.set_gradebook(&course_id, &instructor, &components, &GradeRounding::Nearest, &60);
+    client.add_grader(&course_id, &instructor, &grader);
+
+    client.record_score(&course_id, &instructor, &student, &0, &17);
+    client.record_score(&course_id, &grader, &student, &1, &81);
+    client.record_score(&course_id, &grader, &student, &2, &44);
+
+    // 25.5 + 24.3 + 29.33 = 79.13, rounded to 79
+    assert_eq!(client.compute_grade(&course_id, &student), 79);
+
+    // Per-student scores live in persistent storage, not the shared instance entry
+    env.as_contract(&client.address, || {
+        let score_key = CourseMetadataKey::Score(course_id.clone(), student.clone(), 0);
+        assert!(env.storage().persistent().has(&score_key));
+        assert!(!env.storage().instance().has(&score_key));
+    });
+
+    // Shrinking a component's max score cannot inflate recorded scores
+    let mut shrunk = components.clone();
+    shrunk.set(1, GradeComponent { id: 1, name: String::from_str(&env, "Assignments"), kind: GradeComponentKind::Assignment, weight: 30, max_score: 10 });
+    assert!(client.try_set_gradebook(&course_id, &instructor, &shrunk, &GradeRounding::Nearest, &60).is_err());
+
+    // The caller's grade is ignored in favour of the computed one
+    let completion_id = client.record_completion(
+        &course_id,
+        &student,
+        &100,
+        &String::from_str(&env, "QmCertHash456"),
+        &vec![&env],
+    );
+    let completion = client.get_completion(&completion_id);
+    assert_eq!(completion.final_grade, 79);
 }
@@ -0,0 +1,278 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, String, Vec};
+
+/// Every DID managed by the registry starts with this method prefix
+pub const DID_PREFIX: &[u8] = b"did:stellar:";
+
+/// Upper bound on the byte length of a DID
+pub const MAX_DID_LEN: usize = 128;
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VerificationKey {
+    pub id: String, // fragment, e.g. "key-1"
+    pub public_key: BytesN<32>, // ed25519 public key
+    pub added_at: u64,
+    pub revoked_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ServiceEndpoint {
+    pub id: String,
+    pub service_type: String, // e.g. "LinkedDomains", "CredentialRepository"
+    pub endpoint: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DidDocument {
+    pub id: String,
+    pub controller: Address,
+    pub verification_keys: Vec<VerificationKey>,
+    pub services: Vec<ServiceEndpoint>,
+    pub linked_addresses: Vec<Address>,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub version: u64,
+}
+
+#[contracttype]
+pub enum DidKey {
+    Document(String),
+    AddressDid(Address),
+}
+
+#[contract]
+pub struct DidRegistryContract;
+
+#[contractimpl]
+impl DidRegistryContract {
+    /// Register a new DID document controlled by the caller
+    pub fn create_did(env: Env, controller: Address, did: String, key_id: String, public_key: BytesN<32>) {
+        controller.require_auth();
+
+        Self::validate_did(did.clone());
+        if env.storage().persistent().has(&DidKey::Document(did.clone())) {
+            panic!("DID already registered");
+        }
+        if env.storage().persistent().has(&DidKey::AddressDid(controller.clone())) {
+            panic!("Address already linked to a DID");
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let mut verification_keys = Vec::new(&env);
+        verification_keys.push_back(VerificationKey {
+            id: key_id,
+            public_key,
+            added_at: timestamp,
+            revoked_at: None,
+        });
+
+        let document = DidDocument {
+            id: did.clone(),
+            controller: controller.clone(),
+            verification_keys,
+            services: Vec::new(&env),
+            linked_addresses: Vec::new(&env),
+            created_at: timestamp,
+            updated_at: timestamp,
+            version: 1,
+        };
+
+        env.storage().persistent().set(&DidKey::Document(did.clone()), &document);
+        env.storage().persistent().set(&DidKey::AddressDid(controller), &did);
+    }
+
+    /// Add a verification key to a DID document
+    pub fn add_key(env: Env, did: String, key_id: String, public_key: BytesN<32>) {
+        let mut document = Self::load_for_controller(env.clone(), did);
+
+        if Self::find_key(&document, &key_id).is_some() {
+            panic!("Key id already in use");
+        }
+
+        document.verification_keys.push_back(VerificationKey {
+            id: key_id,
+            public_key,
+            added_at: env.ledger().timestamp(),
+            revoked_at: None,
+        });
+
+        Self::save(env, document);
+    }
+
+    /// Revoke a verification key
+    pub fn revoke_key(env: Env, did: String, key_id: String) {
+        let mut document = Self::load_for_controller(env.clone(), did);
+        Self::revoke(&env, &mut document, &key_id);
+        Self::save(env, document);
+    }
+
+    /// Replace a verification key with a new one in a single update
+    pub fn rotate_key(env: Env, did: String, old_key_id: String, new_key_id: String, new_public_key: BytesN<32>) {
+        let mut document = Self::load_for_controller(env.clone(), did);
+
+        if Self::find_key(&document, &new_key_id).is_some() {
+            panic!("Key id already in use");
+        }
+
+        Self::revoke(&env, &mut document, &old_key_id);
+        document.verification_keys.push_back(VerificationKey {
+            id: new_key_id,
+            public_key: new_public_key,
+            added_at: env.ledger().timestamp(),
+            revoked_at: None,
+        });
+
+        Self::save(env, document);
+    }
+
+    /// Add or replace a service endpoint
+    pub fn set_service(env: Env, did: String, service_id: String, service_type: String, endpoint: String) {
+        let mut document = Self::load_for_controller(env.clone(), did);
+
+        let service = ServiceEndpoint {
+            id: service_id.clone(),
+            service_type,
+            endpoint,
+        };
+
+        match document.services.iter().position(|existing| existing.id == service_id) {
+            Some(index) => document.services.set(index as u32, service),
+            None => document.services.push_back(service),
+        }
+
+        Self::save(env, document);
+    }
+
+    /// Remove a service endpoint
+    pub fn remove_service(env: Env, did: String, service_id: String) {
+        let mut document = Self::load_for_controller(env.clone(), did);
+
+        let index = document.services.iter()
+            .position(|existing| existing.id == service_id)
+            .unwrap_or_else(|| panic!("Service not found"));
+        document.services.remove(index as u32);
+
+        Self::save(env, document);
+    }
+
+    /// Link an additional address to a DID; the address must also authorize
+    pub fn link_address(env: Env, did: String, address: Address) {
+        let mut document = Self::load_for_controller(env.clone(), did.clone());
+        address.require_auth();
+
+        if env.storage().persistent().has(&DidKey::AddressDid(address.clone())) {
+            panic!("Address already linked to a DID");
+        }
+
+        document.linked_addresses.push_back(address.clone());
+        env.storage().persistent().set(&DidKey::AddressDid(address), &did);
+
+        Self::save(env, document);
+    }
+
+    /// Unlink an address from a DID
+    pub fn unlink_address(env: Env, did: String, address: Address) {
+        let mut document = Self::load_for_controller(env.clone(), did);
+
+        let index = document.linked_addresses.first_index_of(&address)
+            .unwrap_or_else(|| panic!("Address not linked"));
+        document.linked_addresses.remove(index);
+        env.storage().persistent().remove(&DidKey::AddressDid(address));
+
+        Self::save(env, document);
+    }
+
+    /// Hand control of a DID to a new wallet; both controllers must authorize
+    pub fn change_controller(env: Env, did: String, new_controller: Address) {
+        let mut document = Self::load_for_controller(env.clone(), did.clone());
+        new_controller.require_auth();
+
+        // The new controller may already be one of the linked addresses
+        if let Some(index) = document.linked_addresses.first_index_of(&new_controller) {
+            document.linked_addresses.remove(index);
+        } else if env.storage().persistent().has(&DidKey::AddressDid(new_controller.clone())) {
+            panic!("Address already linked to a DID");
+        }
+
+        env.storage().persistent().remove(&DidKey::AddressDid(document.controller.clone()));
+        env.storage().persistent().set(&DidKey::AddressDid(new_controller.clone()), &did);
+        document.controller = new_controller;
+
+        Self::save(env, document);
+    }
+
+    /// Resolve a DID to its document
+    pub fn resolve(env: Env, did: String) -> DidDocument {
+        env.storage().persistent()
+            .get(&DidKey::Document(did))
+            .unwrap_or_else(|| panic!("DID not found"))
+    }
+
+    /// Look up the DID an address controls or is linked to
+    pub fn get_did_for_address(env: Env, address: Address) -> Option<String> {
+        env.storage().persistent().get(&DidKey::AddressDid(address))
+    }
+
+    /// Get the keys of a DID that have not been revoked
+    pub fn get_active_keys(env: Env, did: String) -> Vec<VerificationKey> {
+        let document = Self::resolve(env.clone(), did);
+
+        let mut keys = Vec::new(&env);
+        for key in document.verification_keys.iter() {
+            if key.revoked_at.is_none() {
+                keys.push_back(key);
+            }
+        }
+
+        keys
+    }
+
+    /// Load a DID document and require authorization from its controller
+    fn load_for_controller(env: Env, did: String) -> DidDocument {
+        let document = Self::resolve(env, did);
+        document.controller.require_auth();
+        document
+    }
+
+    /// Persist a document, bumping its version
+    fn save(env: Env, mut document: DidDocument) {
+        document.updated_at = env.ledger().timestamp();
+        document.version += 1;
+        env.storage().persistent().set(&DidKey::Document(document.id.clone()), &document);
+    }
+
+    /// Mark an active key as revoked
+    fn revoke(env: &Env, document: &mut DidDocument, key_id: &String) {
+        let index = Self::find_key(document, key_id).unwrap_or_else(|| panic!("Key not found"));
+        let mut key = document.verification_keys.get(index).unwrap();
+        if key.revoked_at.is_some() {
+            panic!("Key already revoked");
+        }
+
+        key.revoked_at = Some(env.ledger().timestamp());
+        document.verification_keys.set(index, key);
+    }
+
+    /// Find the index of a key by id
+    fn find_key(document: &DidDocument, key_id: &String) -> Option<u32> {
+        document.verification_keys.iter()
+            .position(|key| key.id == *key_id)
+            .map(|index| index as u32)
+    }
+
+    /// Check a DID has the did:stellar: prefix and a non-empty identifier
+    fn validate_did(did: String) {
+        let len = did.len() as usize;
+        if len <= DID_PREFIX.len() || len > MAX_DID_LEN {
+            panic!("Invalid DID");
+        }
+
+        let mut buf = [0u8; MAX_DID_LEN];
+        did.copy_into_slice(&mut buf[..len]);
+        if !buf.starts_with(DID_PREFIX) {
+            panic!("Invalid DID");
+        }
+    }
+}
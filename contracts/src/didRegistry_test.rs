@@ -0,0 +1,153 @@
+#![cfg(test)]
+
+use crate::didRegistry::{DidRegistryContract, DidRegistryContractClient};
+use crate::{DisputeSubject, StarkEdContract, StarkEdContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+fn setup(env: &Env) -> DidRegistryContractClient<'_> {
+    let contract_id = env.register_contract(None, DidRegistryContract);
+    env.mock_all_auths();
+    DidRegistryContractClient::new(env, &contract_id)
+}
+
+#[test]
+fn test_did_lifecycle() {
+    let env = Env::default();
+    let client = setup(&env);
+    let wallet = Address::generate(&env);
+    let did = String::from_str(&env, "did:stellar:learner-42");
+    let key_1 = String::from_str(&env, "key-1");
+    let key_2 = String::from_str(&env, "key-2");
+
+    client.create_did(&wallet, &did, &key_1, &BytesN::from_array(&env, &[1; 32]));
+    assert_eq!(client.get_did_for_address(&wallet), Some(did.clone()));
+
+    // Rotate the signing key
+    client.rotate_key(&did, &key_1, &key_2, &BytesN::from_array(&env, &[2; 32]));
+    let active_keys = client.get_active_keys(&did);
+    assert_eq!(active_keys.len(), 1);
+    assert_eq!(active_keys.get(0).unwrap().id, key_2);
+
+    client.set_service(
+        &did,
+        &String::from_str(&env, "portfolio"),
+        &String::from_str(&env, "LinkedDomains"),
+        &String::from_str(&env, "https://example.com"),
+    );
+
+    // Move the identity to a new wallet
+    let new_wallet = Address::generate(&env);
+    client.change_controller(&did, &new_wallet);
+    assert_eq!(client.get_did_for_address(&wallet), None);
+    assert_eq!(client.get_did_for_address(&new_wallet), Some(did.clone()));
+
+    let document = client.resolve(&did);
+    assert_eq!(document.controller, new_wallet);
+    assert_eq!(document.services.len(), 1);
+    assert_eq!(document.verification_keys.len(), 2);
+    assert_eq!(document.version, 4);
+}
+
+#[test]
+fn test_link_address() {
+    let env = Env::default();
+    let client = setup(&env);
+    let wallet = Address::generate(&env);
+    let phone = Address::generate(&env);
+    let did = String::from_str(&env, "did:stellar:learner-7");
+
+    client.create_did(&wallet, &did, &String::from_str(&env, "key-1"), &BytesN::from_array(&env, &[1; 32]));
+    client.link_address(&did, &phone);
+    assert_eq!(client.get_did_for_address(&phone), Some(did.clone()));
+
+    client.unlink_address(&did, &phone);
+    assert_eq!(client.get_did_for_address(&phone), None);
+    assert_eq!(client.resolve(&did).linked_addresses.len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Invalid DID")]
+fn test_invalid_did_prefix() {
+    let env = Env::default();
+    let client = setup(&env);
+    let wallet = Address::generate(&env);
+
+    client.create_did(
+        &wallet,
+        &String::from_str(&env, "did:web:example.com"),
+        &String::from_str(&env, "key-1"),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+}
+
+#[test]
+fn test_issue_credential_to_did() {
+    let env = Env::default();
+    let registry = setup(&env);
+    let wallet = Address::generate(&env);
+    let did = String::from_str(&env, "did:stellar:learner-9");
+    registry.create_did(&wallet, &did, &String::from_str(&env, "key-1"), &BytesN::from_array(&env, &[1; 32]));
+
+    let starked_id = env.register_contract(None, StarkEdContract);
+    let starked = StarkEdContractClient::new(&env, &starked_id);
+    let admin = Address::generate(&env);
+    starked.initialize(&admin);
+    starked.set_did_registry_contract(&admin, &registry.address);
+
+    let credential_id = starked.issue_credential_to_did(
+        &admin,
+        &did,
+        &String::from_str(&env, "Rust Fundamentals"),
+        &String::from_str(&env, "Completed the Rust fundamentals course"),
+        &String::from_str(&env, "course_1"),
+        &String::from_str(&env, "QmCertificateHash"),
+    );
+
+    let credential = starked.get_credential(&credential_id);
+    assert_eq!(credential.recipient, wallet);
+    assert_eq!(credential.subject_did, Some(did.clone()));
+    assert_eq!(starked.get_did_credentials(&did).len(), 1);
+}
+
+#[test]
+fn test_did_credential_follows_controller() {
+    let env = Env::default();
+    let registry = setup(&env);
+    let wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let did = String::from_str(&env, "did:stellar:learner-10");
+    registry.create_did(&wallet, &did, &String::from_str(&env, "key-1"), &BytesN::from_array(&env, &[1; 32]));
+
+    let starked_id = env.register_contract(None, StarkEdContract);
+    let starked = StarkEdContractClient::new(&env, &starked_id);
+    let admin = Address::generate(&env);
+    starked.initialize(&admin);
+    starked.set_did_registry_contract(&admin, &registry.address);
+
+    let credential_id = starked.issue_credential_to_did(
+        &admin,
+        &did,
+        &String::from_str(&env, "Rust Fundamentals"),
+        &String::from_str(&env, "Completed the Rust fundamentals course"),
+        &String::from_str(&env, "course_1"),
+        &String::from_str(&env, "QmCertificateHash"),
+    );
+    let endorser = Address::generate(&env);
+    starked.add_endorser(&admin, &endorser);
+    let endorsement_id = starked.endorse_credential(&endorser, &credential_id, &BytesN::from_array(&env, &[7; 32]), &Some(5));
+
+    registry.change_controller(&did, &new_wallet);
+
+    // The new controller holds the credential; the old wallet no longer does
+    assert_eq!(starked.get_recipient_credentials(&wallet).len(), 0);
+    assert_eq!(starked.get_recipient_credentials(&new_wallet).len(), 1);
+    assert_eq!(starked.get_recipient_credentials(&new_wallet).get(0), Some(credential_id));
+
+    starked.accept_endorsement(&endorsement_id);
+    assert_eq!(starked.get_profile(&new_wallet).reputation, 5);
+
+    let reason = String::from_str(&env, "Wrong title");
+    let evidence = BytesN::from_array(&env, &[2; 32]);
+    assert!(starked.try_open_dispute(&wallet, &DisputeSubject::Credential(credential_id), &reason, &evidence).is_err());
+    starked.open_dispute(&new_wallet, &DisputeSubject::Credential(credential_id), &reason, &evidence);
+}
@@ -1,8 +1,8 @@
-#![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec, symbol_short};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol, Vec, symbol_short};
+use crate::analyticsStorage::AnalyticsContractClient;
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum EventType {
     CourseCompletion,
     CredentialIssuance,
@@ -30,6 +30,9 @@ pub enum EventKey {
     UserEvents(Address),
     EventTypeEvents(EventType),
     EventCount,
+    AnalyticsContract,
+    Admin,
+    Issuer(Address),
 }
 
 #[contract]
@@ -37,15 +40,41 @@ pub struct EventLoggerContract;
 
 #[contractimpl]
 impl EventLoggerContract {
-    /// Initialize the contract
-    pub fn initialize(env: Env) {
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) {
         if env.storage().instance().has(&EventKey::EventCount) {
             panic!("Contract already initialized");
         }
         
+        env.storage().instance().set(&EventKey::Admin, &admin);
         env.storage().instance().set(&EventKey::EventCount, &0u64);
     }
 
+    /// Set the AnalyticsContract that counts logged events (Admin only)
+    pub fn set_analytics_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&EventKey::AnalyticsContract, &contract);
+    }
+
+    /// Approve an address, typically a contract, to log credential issuances (Admin only)
+    pub fn add_issuer(env: Env, admin: Address, issuer: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().set(&EventKey::Issuer(issuer), &true);
+    }
+
+    /// Withdraw an issuer's approval (Admin only)
+    pub fn remove_issuer(env: Env, admin: Address, issuer: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().remove(&EventKey::Issuer(issuer));
+    }
+
+    /// Check whether an address is an approved credential issuer
+    pub fn is_issuer(env: Env, issuer: Address) -> bool {
+        env.storage().persistent()
+            .get(&EventKey::Issuer(issuer))
+            .unwrap_or(false)
+    }
+
     /// Log a course completion event
     pub fn log_course_completion(
         env: Env,
@@ -74,16 +103,23 @@ impl EventLoggerContract {
         event_id
     }
 
-    /// Log a credential issuance event
+    /// Log a credential issuance event (Admin or approved issuers)
     pub fn log_credential_issuance(
         env: Env,
+        issuer: Address,
         user: Address,
         credential_id: u64,
         course_id: String,
         metadata: String,
     ) -> u64 {
-        // In production, require admin auth
-        // user.require_auth();
+        issuer.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&EventKey::Admin)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        if issuer != admin && !Self::is_issuer(env.clone(), issuer.clone()) {
+            panic!("Only admin or approved issuers can log credential issuance");
+        }
         
         let event_id = Self::create_event(
             env.clone(),
@@ -97,7 +133,7 @@ impl EventLoggerContract {
         
         // Create notification for credential issuance
         env.events().publish(
-            (symbol_short!("credential"), symbol_short!("issued")),
+            (Symbol::new(&env, "credential"), symbol_short!("issued")),
             (user, credential_id, event_id)
         );
         
@@ -125,7 +161,7 @@ impl EventLoggerContract {
         
         // Create notification for achievement
         env.events().publish(
-            (symbol_short!("achievement"), symbol_short!("earned")),
+            (Symbol::new(&env, "achievement"), symbol_short!("earned")),
             (user, event_id)
         );
         
@@ -140,7 +176,7 @@ impl EventLoggerContract {
     ) -> u64 {
         user.require_auth();
         
-        let event_id = Self::create_event(
+        Self::create_event(
             env.clone(),
             EventType::ProfileUpdate,
             user.clone(),
@@ -148,9 +184,7 @@ impl EventLoggerContract {
             None,
             None,
             metadata,
-        );
-        
-        event_id
+        )
     }
 
     /// Log a course enrollment event
@@ -162,7 +196,7 @@ impl EventLoggerContract {
     ) -> u64 {
         user.require_auth();
         
-        let event_id = Self::create_event(
+        Self::create_event(
             env.clone(),
             EventType::CourseEnrollment,
             user.clone(),
@@ -170,9 +204,7 @@ impl EventLoggerContract {
             None,
             None,
             metadata,
-        );
-        
-        event_id
+        )
     }
 
     /// Get event by ID
@@ -188,7 +220,7 @@ impl EventLoggerContract {
         
         let mut events = Vec::new(&env);
         for event_id in event_ids.iter() {
-            if let Some(event) = Self::get_event(env.clone(), *event_id) {
+            if let Some(event) = Self::get_event(env.clone(), event_id) {
                 events.push_back(event);
             }
         }
@@ -204,7 +236,7 @@ impl EventLoggerContract {
         
         let mut events = Vec::new(&env);
         for event_id in event_ids.iter() {
-            if let Some(event) = Self::get_event(env.clone(), *event_id) {
+            if let Some(event) = Self::get_event(env.clone(), event_id) {
                 events.push_back(event);
             }
         }
@@ -219,17 +251,8 @@ impl EventLoggerContract {
             .unwrap_or(0);
         
         let mut events = Vec::new(&env);
-        let start = if total_events > offset as u64 { 
-            total_events - offset as u64 
-        } else { 
-            0 
-        };
-        
-        let end = if start > limit as u64 { 
-            start - limit as u64 
-        } else { 
-            0 
-        };
+        let start = total_events.saturating_sub(offset as u64);
+        let end = start.saturating_sub(limit as u64);
         
         for i in (end..start).rev() {
             if let Some(event) = Self::get_event(env.clone(), i + 1) {
@@ -273,6 +296,10 @@ impl EventLoggerContract {
             metadata,
         };
         
+        if let Some(analytics) = env.storage().instance().get::<_, Address>(&EventKey::AnalyticsContract) {
+            AnalyticsContractClient::new(&env, &analytics).on_event(&user, &event_type);
+        }
+
         // Store the event
         env.storage().instance().set(&EventKey::Event(event_id), &event);
         env.storage().instance().set(&EventKey::EventCount, &event_id);
@@ -286,11 +313,23 @@ impl EventLoggerContract {
         
         // Update event type list
         let mut type_events: Vec<u64> = env.storage().instance()
-            .get(&EventKey::EventTypeEvents(event_type))
+            .get(&EventKey::EventTypeEvents(event_type.clone()))
             .unwrap_or_else(|| Vec::new(&env));
         type_events.push_back(event_id);
         env.storage().instance().set(&EventKey::EventTypeEvents(event_type), &type_events);
         
         event_id
     }
-}
\ No newline at end of file
+    /// Require authorization from the admin address
+    fn require_admin(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&EventKey::Admin)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+
+        if caller != admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use crate::StarkEdContractClient;
+use crate::courseMetadata::CourseMetadataContractClient;
+use crate::progress::CourseProgressContractClient;
+
+/// Upper bound on the number of courses in a learning path
+pub const MAX_PATH_COURSES: u32 = 32;
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PathStep {
+    pub course_id: String,
+    pub prerequisites: Vec<String>, // earlier courses of the path that must be finished first
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LearningPath {
+    pub id: u64,
+    pub creator: Address,
+    pub title: String,
+    pub description: String,
+    pub steps: Vec<PathStep>,
+    pub is_sequential: bool, // every course requires the one before it
+    pub credential_hash: String, // IPFS hash of the path certificate
+    pub is_active: bool,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathProgress {
+    pub path_id: u64,
+    pub courses_completed: u32,
+    pub total_courses: u32,
+    pub progress_bps: u32, // Basis points (0-10000), averaged over the path's courses
+    pub available_courses: Vec<String>, // unfinished courses whose prerequisites are finished
+    pub is_completed: bool,
+    pub credential_id: Option<u64>,
+}
+
+#[contracttype]
+pub enum PathKey {
+    Path(u64),
+    PathCount,
+    Admin,
+    Issuer(Address),
+    CourseMetadataContract,
+    ProgressContract,
+    CredentialContract,
+}
+
+#[contract]
+pub struct LearningPathContract;
+
+#[contractimpl]
+impl LearningPathContract {
+    /// Initialize the contract with the course metadata and progress contracts paths are built on
+    pub fn initialize(env: Env, admin: Address, course_metadata_contract: Address, progress_contract: Address) {
+        if env.storage().instance().has(&PathKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&PathKey::Admin, &admin);
+        env.storage().instance().set(&PathKey::CourseMetadataContract, &course_metadata_contract);
+        env.storage().instance().set(&PathKey::ProgressContract, &progress_contract);
+        env.storage().instance().set(&PathKey::PathCount, &0u64);
+    }
+
+    /// Set the StarkEdContract that issues path credentials (Admin only)
+    pub fn set_credential_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&PathKey::CredentialContract, &contract);
+    }
+
+    /// Approve an address to create learning paths (Admin only)
+    pub fn add_issuer(env: Env, admin: Address, issuer: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().set(&PathKey::Issuer(issuer), &true);
+    }
+
+    /// Withdraw an issuer's approval; paths it already created are unaffected (Admin only)
+    pub fn remove_issuer(env: Env, admin: Address, issuer: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().remove(&PathKey::Issuer(issuer));
+    }
+
+    /// Check whether an address is an approved path issuer
+    pub fn is_issuer(env: Env, issuer: Address) -> bool {
+        env.storage().persistent()
+            .get(&PathKey::Issuer(issuer))
+            .unwrap_or(false)
+    }
+
+    /// Create a learning path from courses listed in teaching order (Admin or approved issuers)
+    pub fn create_path(
+        env: Env,
+        creator: Address,
+        title: String,
+        description: String,
+        course_ids: Vec<String>,
+        is_sequential: bool,
+        credential_hash: String,
+    ) -> u64 {
+        creator.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&PathKey::Admin)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        if creator != admin && !Self::is_issuer(env.clone(), creator.clone()) {
+            panic!("Only admin or approved issuers can create paths");
+        }
+
+        if course_ids.is_empty() || course_ids.len() > MAX_PATH_COURSES {
+            panic!("Path course count out of range");
+        }
+
+        let course_contract: Address = env.storage().instance()
+            .get(&PathKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        let courses = CourseMetadataContractClient::new(&env, &course_contract);
+
+        let mut steps: Vec<PathStep> = Vec::new(&env);
+        for (index, course_id) in course_ids.iter().enumerate() {
+            if steps.iter().any(|step| step.course_id == course_id) {
+                panic!("Course listed twice");
+            }
+
+            let course = match courses.try_get_course(&course_id) {
+                Ok(Ok(course)) => course,
+                _ => panic!("Course not found"),
+            };
+
+            // Prerequisites that are part of the path must be taught before the course
+            let mut prerequisites = Vec::new(&env);
+            for prerequisite in course.prerequisites.iter() {
+                if steps.iter().any(|step| step.course_id == prerequisite) {
+                    prerequisites.push_back(prerequisite);
+                } else if course_ids.contains(&prerequisite) {
+                    panic!("Prerequisite must come earlier in the path");
+                }
+            }
+
+            if is_sequential && index > 0 {
+                let previous = course_ids.get(index as u32 - 1).unwrap();
+                if !prerequisites.contains(&previous) {
+                    prerequisites.push_back(previous);
+                }
+            }
+
+            steps.push_back(PathStep { course_id, prerequisites });
+        }
+
+        let path_id: u64 = env.storage().instance()
+            .get(&PathKey::PathCount)
+            .unwrap_or(0) + 1;
+        let path = LearningPath {
+            id: path_id,
+            creator,
+            title,
+            description,
+            steps,
+            is_sequential,
+            credential_hash,
+            is_active: true,
+            created_at: env.ledger().timestamp(),
+        };
+
+        env.storage().persistent().set(&PathKey::Path(path_id), &path);
+        env.storage().instance().set(&PathKey::PathCount, &path_id);
+
+        path_id
+    }
+
+    /// Stop a path from issuing new credentials (path creator only)
+    pub fn deactivate_path(env: Env, path_id: u64) {
+        let mut path = Self::get_path(env.clone(), path_id);
+        path.creator.require_auth();
+
+        path.is_active = false;
+        env.storage().persistent().set(&PathKey::Path(path_id), &path);
+    }
+
+    /// Get a learning path
+    pub fn get_path(env: Env, path_id: u64) -> LearningPath {
+        env.storage().persistent()
+            .get(&PathKey::Path(path_id))
+            .unwrap_or_else(|| panic!("Learning path not found"))
+    }
+
+    /// Get the number of learning paths created
+    pub fn get_path_count(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&PathKey::PathCount)
+            .unwrap_or(0)
+    }
+
+    /// Compute a learner's progress through a path; only verified completions finish a course
+    pub fn get_path_progress(env: Env, path_id: u64, learner: Address) -> PathProgress {
+        let path = Self::get_path(env.clone(), path_id);
+
+        let course_contract: Address = env.storage().instance()
+            .get(&PathKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        let progress_contract: Address = env.storage().instance()
+            .get(&PathKey::ProgressContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        let courses = CourseMetadataContractClient::new(&env, &course_contract);
+        let progress = CourseProgressContractClient::new(&env, &progress_contract);
+
+        // Self-reported lesson progress only adds partial credit; finishing a course takes a verified completion
+        let mut completed_courses: Vec<String> = Vec::new(&env);
+        for completion_id in courses.get_student_completions(&learner).iter() {
+            let completion = courses.get_completion(&completion_id);
+            if completion.is_verified {
+                completed_courses.push_back(completion.course_id);
+            }
+        }
+
+        let mut total_bps = 0u64;
+        for step in path.steps.iter() {
+            if completed_courses.contains(&step.course_id) {
+                total_bps += 10000;
+                continue;
+            }
+
+            if let Some(course_progress) = progress.get_progress(&learner, &step.course_id) {
+                total_bps += (course_progress.lessons_completed as u64 * 10000 / course_progress.total_lessons as u64).min(10000);
+            }
+        }
+
+        let mut courses_completed = 0;
+        let mut available_courses = Vec::new(&env);
+        for step in path.steps.iter() {
+            if completed_courses.contains(&step.course_id) {
+                courses_completed += 1;
+            } else if step.prerequisites.iter().all(|prerequisite| completed_courses.contains(&prerequisite)) {
+                available_courses.push_back(step.course_id);
+            }
+        }
+
+        let credential_id = env.storage().instance()
+            .get::<_, Address>(&PathKey::CredentialContract)
+            .and_then(|credential_contract| {
+                StarkEdContractClient::new(&env, &credential_contract).get_path_credential(&path_id, &learner)
+            });
+
+        PathProgress {
+            path_id,
+            courses_completed,
+            total_courses: path.steps.len(),
+            progress_bps: (total_bps / path.steps.len() as u64) as u32,
+            available_courses,
+            is_completed: courses_completed == path.steps.len(),
+            credential_id,
+        }
+    }
+
+    /// Issue the path credential to a learner who has finished every course of the path
+    pub fn claim_path_credential(env: Env, path_id: u64, learner: Address) -> u64 {
+        learner.require_auth();
+
+        let path = Self::get_path(env.clone(), path_id);
+        if !path.is_active {
+            panic!("Learning path is not active");
+        }
+
+        let path_progress = Self::get_path_progress(env.clone(), path_id, learner.clone());
+        if !path_progress.is_completed {
+            panic!("Learning path not completed");
+        }
+        if path_progress.credential_id.is_some() {
+            panic!("Credential already issued for path");
+        }
+
+        let credential_contract: Address = env.storage().instance()
+            .get(&PathKey::CredentialContract)
+            .unwrap_or_else(|| panic!("Credential contract not set"));
+
+        StarkEdContractClient::new(&env, &credential_contract).issue_for_path(
+            &path_id,
+            &learner,
+            &path.title,
+            &path.description,
+            &path.credential_hash,
+        )
+    }
+
+    /// Require authorization from the admin address
+    fn require_admin(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&PathKey::Admin)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+
+        if caller != admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+}
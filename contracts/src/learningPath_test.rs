@@ -0,0 +1,220 @@
+#![cfg(test)]
+
+use crate::learningPath::{LearningPathContract, LearningPathContractClient};
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, NewCourse};
+use crate::progress::{CourseProgressContract, CourseProgressContractClient};
+use crate::{StarkEdContract, StarkEdContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
+
+struct Setup<'a> {
+    paths: LearningPathContractClient<'a>,
+    courses: CourseMetadataContractClient<'a>,
+    progress: CourseProgressContractClient<'a>,
+    credentials: StarkEdContractClient<'a>,
+    admin: Address,
+    instructor: Address,
+}
+
+fn setup<'a>(env: &Env) -> Setup<'a> {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+
+    let courses = CourseMetadataContractClient::new(env, &env.register_contract(None, CourseMetadataContract));
+    courses.initialize(&admin);
+
+    let progress = CourseProgressContractClient::new(env, &env.register_contract(None, CourseProgressContract));
+    progress.initialize(&admin, &courses.address);
+
+    let credentials = StarkEdContractClient::new(env, &env.register_contract(None, StarkEdContract));
+    credentials.initialize(&admin);
+
+    let paths = LearningPathContractClient::new(env, &env.register_contract(None, LearningPathContract));
+    paths.initialize(&admin, &courses.address, &progress.address);
+    paths.set_credential_contract(&admin, &credentials.address);
+    credentials.set_learning_path_contract(&admin, &paths.address);
+
+    let instructor = Address::generate(env);
+    paths.add_issuer(&admin, &instructor);
+
+    Setup { paths, courses, progress, credentials, admin, instructor }
+}
+
+fn create_course(env: &Env, setup: &Setup, title: &str, prerequisites: Vec<String>) -> String {
+    let course_id = setup.courses.create_course(
+        &setup.instructor,
+        &NewCourse {
+            title: String::from_str(env, title),
+            description: String::from_str(env, "Part of the Stellar developer path"),
+            category: String::from_str(env, "Programming"),
+            level: String::from_str(env, "intermediate"),
+            duration: 10,
+            price: 0,
+            prerequisites: prerequisites.clone(),
+            learning_objectives: vec![env],
+            syllabus: String::from_str(env, "QmSyllabus"),
+            thumbnail_url: String::from_str(env, "https://example.com/thumbnail.jpg"),
+            tags: vec![env],
+            language: String::from_str(env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+    setup.courses.set_course_lessons(&course_id, &setup.instructor, &2);
+    course_id
+}
+
+/// Record and verify a learner's completion of a course
+fn complete_course(env: &Env, setup: &Setup, learner: &Address, course_id: &String) {
+    let completion_id = setup.courses.record_completion(
+        course_id,
+        learner,
+        &88,
+        &String::from_str(env, "QmCertHash"),
+        &vec![env],
+    );
+    setup.courses.verify_completion(&completion_id, &setup.instructor);
+}
+
+#[test]
+fn test_learning_path_progress_and_credential() {
+    let env = Env::default();
+    let setup = setup(&env);
+    let learner = Address::generate(&env);
+
+    let basics = create_course(&env, &setup, "Stellar Basics", vec![&env]);
+    let contracts = create_course(&env, &setup, "Soroban Contracts", vec![&env, basics.clone()]);
+    let frontend = create_course(&env, &setup, "dApp Frontends", vec![&env, basics.clone()]);
+
+    let path_id = setup.paths.create_path(
+        &setup.instructor,
+        &String::from_str(&env, "Full-stack Stellar developer"),
+        &String::from_str(&env, "From the basics to a deployed dApp"),
+        &vec![&env, basics.clone(), contracts.clone(), frontend.clone()],
+        &false,
+        &String::from_str(&env, "QmPathCertificate"),
+    );
+
+    let path_progress = setup.paths.get_path_progress(&path_id, &learner);
+    assert_eq!(path_progress.available_courses, vec![&env, basics.clone()]);
+
+    // Self-reported lesson progress alone does not finish a course
+    setup.progress.record_progress(&learner, &basics, &2);
+    let path_progress = setup.paths.get_path_progress(&path_id, &learner);
+    assert_eq!(path_progress.courses_completed, 0);
+    assert_eq!(path_progress.available_courses, vec![&env, basics.clone()]);
+
+    // A verified completion of the basics unlocks both follow-up courses
+    complete_course(&env, &setup, &learner, &basics);
+    setup.progress.record_progress(&learner, &contracts, &1);
+    let path_progress = setup.paths.get_path_progress(&path_id, &learner);
+    assert_eq!(path_progress.courses_completed, 1);
+    assert_eq!(path_progress.progress_bps, 5000);
+    assert_eq!(path_progress.available_courses, vec![&env, contracts.clone(), frontend.clone()]);
+
+    // An unverified completion does not count either
+    let completion_id = setup.courses.record_completion(
+        &frontend,
+        &learner,
+        &88,
+        &String::from_str(&env, "QmCertHash"),
+        &vec![&env],
+    );
+    complete_course(&env, &setup, &learner, &contracts);
+    assert!(!setup.paths.get_path_progress(&path_id, &learner).is_completed);
+
+    setup.courses.verify_completion(&completion_id, &setup.instructor);
+    assert!(setup.paths.get_path_progress(&path_id, &learner).is_completed);
+
+    let credential_id = setup.paths.claim_path_credential(&path_id, &learner);
+    let credential = setup.credentials.get_credential(&credential_id);
+    assert_eq!(credential.recipient, learner);
+    assert_eq!(credential.path_id, Some(path_id));
+    assert_eq!(setup.paths.get_path_progress(&path_id, &learner).credential_id, Some(credential_id));
+}
+
+#[test]
+#[should_panic(expected = "Prerequisite must come earlier in the path")]
+fn test_learning_path_order_follows_prerequisites() {
+    let env = Env::default();
+    let setup = setup(&env);
+
+    let basics = create_course(&env, &setup, "Stellar Basics", vec![&env]);
+    let contracts = create_course(&env, &setup, "Soroban Contracts", vec![&env, basics.clone()]);
+
+    setup.paths.create_path(
+        &setup.instructor,
+        &String::from_str(&env, "Backwards"),
+        &String::from_str(&env, "Lists a course before its prerequisite"),
+        &vec![&env, contracts, basics],
+        &false,
+        &String::from_str(&env, "QmPathCertificate"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Learning path not completed")]
+fn test_path_credential_requires_completion() {
+    let env = Env::default();
+    let setup = setup(&env);
+    let learner = Address::generate(&env);
+
+    let basics = create_course(&env, &setup, "Stellar Basics", vec![&env]);
+    let contracts = create_course(&env, &setup, "Soroban Contracts", vec![&env]);
+
+    let path_id = setup.paths.create_path(
+        &setup.instructor,
+        &String::from_str(&env, "Stellar developer"),
+        &String::from_str(&env, "Two courses in sequence"),
+        &vec![&env, basics.clone(), contracts],
+        &true,
+        &String::from_str(&env, "QmPathCertificate"),
+    );
+
+    complete_course(&env, &setup, &learner, &basics);
+    setup.paths.claim_path_credential(&path_id, &learner);
+}
+
+#[test]
+#[should_panic(expected = "Only admin or approved issuers can create paths")]
+fn test_path_creation_requires_approved_issuer() {
+    let env = Env::default();
+    let setup = setup(&env);
+
+    let basics = create_course(&env, &setup, "Stellar Basics", vec![&env]);
+    setup.paths.remove_issuer(&setup.admin, &setup.instructor);
+    assert!(!setup.paths.is_issuer(&setup.instructor));
+
+    setup.paths.create_path(
+        &setup.instructor,
+        &String::from_str(&env, "Stellar developer"),
+        &String::from_str(&env, "Created by an unapproved address"),
+        &vec![&env, basics],
+        &false,
+        &String::from_str(&env, "QmPathCertificate"),
+    );
+}
+
+#[test]
+fn test_path_partial_credit_is_capped_per_course() {
+    let env = Env::default();
+    let setup = setup(&env);
+    let learner = Address::generate(&env);
+
+    let basics = create_course(&env, &setup, "Stellar Basics", vec![&env]);
+    let contracts = create_course(&env, &setup, "Soroban Contracts", vec![&env]);
+
+    let path_id = setup.paths.create_path(
+        &setup.instructor,
+        &String::from_str(&env, "Stellar developer"),
+        &String::from_str(&env, "Two courses in sequence"),
+        &vec![&env, basics.clone(), contracts],
+        &false,
+        &String::from_str(&env, "QmPathCertificate"),
+    );
+
+    // Progress recorded against a longer course still counts as one course at most
+    setup.progress.record_progress(&learner, &basics, &2);
+    setup.courses.set_course_lessons(&basics, &setup.instructor, &1);
+    setup.progress.complete_lesson(&learner, &basics, &0);
+    assert_eq!(setup.paths.get_path_progress(&path_id, &learner).progress_bps, 5000);
+}
@@ -1,16 +1,58 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Vec, String};
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Vec, String};
 
 pub mod progress;
+#[allow(non_snake_case)]
 pub mod eventLogger;
+#[allow(non_snake_case)]
 pub mod courseMetadata;
+#[allow(non_snake_case)]
 pub mod syncCoordination;
+#[allow(non_snake_case)]
+pub mod didRegistry;
+#[allow(non_snake_case)]
+pub mod learningPath;
+pub mod quiz;
+#[allow(non_snake_case)]
+pub mod peerReview;
+#[allow(non_snake_case)]
+pub mod proctorRegistry;
+#[allow(non_snake_case)]
+pub mod analyticsStorage;
+
+use courseMetadata::{CourseMetadataContractClient, CreditSystem};
+use didRegistry::DidRegistryContractClient;
+use proctorRegistry::{ProctorClearance, ProctorRegistryContractClient};
 #[cfg(test)]
 mod progress_test;
 #[cfg(test)]
+#[allow(non_snake_case)]
+mod eventLogger_test;
+#[cfg(test)]
+#[allow(non_snake_case)]
 mod courseMetadata_test;
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod syncCoordination_test;
+#[cfg(test)]
+mod lib_test;
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod didRegistry_test;
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod learningPath_test;
+#[cfg(test)]
+mod quiz_test;
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod peerReview_test;
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod proctorRegistry_test;
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analyticsStorage_test;
 
 
 #[contracttype]
@@ -18,8 +60,70 @@ pub enum DataKey {
     Credential(u64),
     CredentialCount,
     Admin,
+    Endorsement(u64),
+    EndorsementCount,
+    Endorser(Address), // admin-approved endorsers whose endorsements carry reputation weight
+    EndorserEndorsements(Address),
+    CredentialEndorsements(u64),
+    RecipientCredentials(Address),
+    CourseMetadataContract,
+    Transcript(u64),
+    TranscriptCount,
+    LearnerTranscripts(Address),
+    Template(u64),
+    TemplateCount,
+    CourseTemplates(String),
+    Arbitrator(Address),
+    Dispute(u64),
+    DisputeCount,
+    DisputeLog(u64),
+    DisputeResponse(u64),
+    LearnerDisputes(Address),
+    OpenDispute(DisputeSubject), // the unresolved dispute on a credential or completion, if any
+    DidRegistryContract,
+    DidCredentials(String),
+    CompletionCredential(String),
+    LearningPathContract,
+    PathCredential(u64, Address),
+    ProctorRegistryContract,
 }
 
+/// Upper bound on the byte length of the prefix passed to `numbered_id`
+pub const MAX_ID_PREFIX_LEN: usize = 32;
+
+/// Build a counter-based id such as `course_12`; `no_std` has no `format!`
+pub(crate) fn numbered_id(env: &Env, prefix: &str, number: u64) -> String {
+    let prefix = prefix.as_bytes();
+    if prefix.len() > MAX_ID_PREFIX_LEN {
+        panic!("Id prefix too long");
+    }
+
+    let mut buf = [0u8; MAX_ID_PREFIX_LEN + 20];
+    buf[..prefix.len()].copy_from_slice(prefix);
+
+    let mut digits = [0u8; 20];
+    let mut digits_start = digits.len();
+    let mut remaining = number;
+    loop {
+        digits_start -= 1;
+        digits[digits_start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    let len = prefix.len() + digits.len() - digits_start;
+    buf[prefix.len()..len].copy_from_slice(&digits[digits_start..]);
+    String::from_bytes(env, &buf[..len])
+}
+
+/// Upper bound on the weight a single endorsement can carry
+pub const MAX_ENDORSEMENT_WEIGHT: u32 = 100;
+
+/// Upper bound on the byte length of template title patterns and rendered titles
+pub const MAX_TITLE_LEN: usize = 128;
+
 #[contracttype]
 pub struct Credential {
     pub id: u64,
@@ -31,6 +135,132 @@ pub struct Credential {
     pub completion_date: u64,
     pub ipfs_hash: String,
     pub is_verified: bool,
+    pub credits: u32, // hundredths of a credit, from the course metadata if known
+    pub credit_system: CreditSystem,
+    pub expires_at: Option<u64>,
+    pub template_id: Option<u64>,
+    pub superseded_by: Option<u64>, // replacement credential after a dispute
+    pub subject_did: Option<String>, // did:stellar: subject, survives wallet changes
+    pub completion_id: Option<String>, // CourseMetadataContract completion this was issued for
+    pub path_id: Option<u64>, // LearningPathContract path this was issued for
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EndorsementStatus {
+    Pending,
+    Accepted,
+    Hidden,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Endorsement {
+    pub id: u64,
+    pub credential_id: u64,
+    pub endorser: Address, // employer, instructor or professional body
+    pub claim_hash: BytesN<32>, // hash of the off-chain endorsement claim
+    pub weight: u32, // 1-MAX_ENDORSEMENT_WEIGHT, as submitted by the endorser
+    pub applied_weight: u32, // reputation currently granted: the weight if accepted from an approved endorser, else 0
+    pub status: EndorsementStatus,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeSubject {
+    Credential(u64),
+    Completion(String), // CourseMetadataContract completion id
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Responded,
+    Resolved,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeOutcome {
+    Pending, // not yet resolved
+    Rejected,
+    Amended,
+    Reissued,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeAction {
+    Opened,
+    Responded,
+    Resolved,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Dispute {
+    pub id: u64,
+    pub subject: DisputeSubject,
+    pub learner: Address,
+    pub reason: String,
+    pub evidence_hash: BytesN<32>,
+    pub status: DisputeStatus,
+    pub outcome: DisputeOutcome,
+    pub arbitrator: Option<Address>,
+    pub replacement_credential_id: Option<u64>,
+    pub opened_at: u64,
+    pub resolved_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeLogEntry {
+    pub action: DisputeAction,
+    pub actor: Address,
+    pub documents: Vec<BytesN<32>>, // evidence or response hash, empty when the step attached none
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CredentialTemplate {
+    pub id: u64,
+    pub course_id: String,
+    pub issuer: Address,
+    pub title_pattern: String, // may contain {course} and {grade} placeholders
+    pub description: String,
+    pub design_cid: String, // IPFS CID of the certificate design
+    pub validity_period: Option<u64>, // seconds until expiry, None for no expiry
+    pub min_grade: u32, // 0-100
+    pub is_active: bool,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TranscriptEntry {
+    pub title: String,
+    pub course_id: String,
+    pub grade: Option<u32>, // 0-100, from the verified completion if any
+    pub completed_at: u64,
+    pub credits: u32, // hundredths of a credit
+    pub credit_system: CreditSystem,
+    pub credential_id: Option<u64>,
+    pub completion_id: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    pub id: u64,
+    pub learner: Address,
+    pub entries: Vec<TranscriptEntry>, // ordered by completion date, then course id
+    pub digest: BytesN<32>, // SHA-256 of the XDR-encoded entries
+    pub compiled_at: u64,
+    pub sealed_by: Option<Address>,
+    pub sealed_at: Option<u64>,
 }
 
 #[contracttype]
@@ -84,42 +314,181 @@ impl StarkEdContract {
             panic!("Only admin can issue credentials");
         }
 
-        let count: u64 = env.storage().instance()
-            .get(&DataKey::CredentialCount)
-            .unwrap_or(0);
-        let credential_id = count + 1;
+        Self::store_credential(env, issuer, recipient, title, description, course_id, ipfs_hash, None, None, None)
+    }
 
-        let credential = Credential {
-            id: credential_id,
-            issuer: issuer.clone(),
-            recipient: recipient.clone(),
+    /// Issue the credential for a verified completion (CourseMetadataContract only)
+    pub fn issue_for_completion(
+        env: Env,
+        completion_id: String,
+        recipient: Address,
+        course_id: String,
+        title: String,
+        description: String,
+        certificate_hash: String,
+    ) -> u64 {
+        let course_contract: Address = env.storage().instance()
+            .get(&DataKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Course metadata contract not set"));
+        course_contract.require_auth();
+
+        if env.storage().persistent().has(&DataKey::CompletionCredential(completion_id.clone())) {
+            panic!("Credential already issued for completion");
+        }
+        Self::require_proctor_clearance(env.clone(), course_id.clone(), recipient.clone());
+
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Admin not found"));
+
+        let credential_id = Self::store_credential(
+            env.clone(),
+            admin,
+            recipient,
             title,
             description,
             course_id,
-            completion_date: env.ledger().timestamp(),
-            ipfs_hash,
-            is_verified: false,
-        };
+            certificate_hash,
+            None,
+            None,
+            None,
+        );
 
+        let mut credential = Self::get_credential(env.clone(), credential_id);
+        credential.completion_id = Some(completion_id.clone());
         env.storage().instance().set(&DataKey::Credential(credential_id), &credential);
-        env.storage().instance().set(&DataKey::CredentialCount, &credential_id);
+        env.storage().persistent().set(&DataKey::CompletionCredential(completion_id), &credential_id);
 
         credential_id
     }
 
-    /// Verify a credential
-    pub fn verify_credential(env: Env, credential_id: u64) -> bool {
+    /// Get the credential issued for a completion, if any
+    pub fn get_completion_credential(env: Env, completion_id: String) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::CompletionCredential(completion_id))
+    }
+
+    /// Issue the credential for a finished learning path (LearningPathContract only)
+    pub fn issue_for_path(
+        env: Env,
+        path_id: u64,
+        recipient: Address,
+        title: String,
+        description: String,
+        credential_hash: String,
+    ) -> u64 {
+        let path_contract: Address = env.storage().instance()
+            .get(&DataKey::LearningPathContract)
+            .unwrap_or_else(|| panic!("Learning path contract not set"));
+        path_contract.require_auth();
+
+        let path_key = DataKey::PathCredential(path_id, recipient.clone());
+        if env.storage().persistent().has(&path_key) {
+            panic!("Credential already issued for path");
+        }
+
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Admin not found"));
+
+        // Path credentials are not tied to a single course
+        let credential_id = Self::store_credential(
+            env.clone(),
+            admin,
+            recipient,
+            title,
+            description,
+            String::from_str(&env, ""),
+            credential_hash,
+            None,
+            None,
+            None,
+        );
+
+        let mut credential = Self::get_credential(env.clone(), credential_id);
+        credential.path_id = Some(path_id);
+        env.storage().instance().set(&DataKey::Credential(credential_id), &credential);
+        env.storage().persistent().set(&path_key, &credential_id);
+
+        credential_id
+    }
+
+    /// Get the credential issued to a learner for a learning path, if any
+    pub fn get_path_credential(env: Env, path_id: u64, recipient: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::PathCredential(path_id, recipient))
+    }
+
+    /// Point the contract at the proctor registry that gates credentials for proctored courses (Admin only)
+    pub fn set_proctor_registry_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&DataKey::ProctorRegistryContract, &contract);
+    }
+
+    /// Point the contract at the LearningPathContract allowed to issue path credentials (Admin only)
+    pub fn set_learning_path_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&DataKey::LearningPathContract, &contract);
+    }
+
+    /// Issue a credential to the holder of a DID (Admin only)
+    pub fn issue_credential_to_did(
+        env: Env,
+        issuer: Address,
+        subject_did: String,
+        title: String,
+        description: String,
+        course_id: String,
+        ipfs_hash: String,
+    ) -> u64 {
+        Self::require_admin(env.clone(), issuer.clone());
+
+        let registry: Address = env.storage().instance()
+            .get(&DataKey::DidRegistryContract)
+            .unwrap_or_else(|| panic!("DID registry contract not set"));
+        let document = DidRegistryContractClient::new(&env, &registry).resolve(&subject_did);
+
+        Self::store_credential(
+            env,
+            issuer,
+            document.controller,
+            title,
+            description,
+            course_id,
+            ipfs_hash,
+            None,
+            None,
+            Some(subject_did),
+        )
+    }
+
+    /// Get the ids of all credentials issued to a DID
+    pub fn get_did_credentials(env: Env, subject_did: String) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&DataKey::DidCredentials(subject_did))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Point the contract at the DID registry used to resolve credential subjects (Admin only)
+    pub fn set_did_registry_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&DataKey::DidRegistryContract, &contract);
+    }
+
+    /// Verify a credential (Admin or the credential's issuer)
+    pub fn verify_credential(env: Env, verifier: Address, credential_id: u64) -> bool {
+        verifier.require_auth();
+
         let admin: Address = env.storage().instance()
             .get(&DataKey::Admin)
             .unwrap_or_else(|| panic!("Admin not found"));
 
-        // In production, this would require admin authorization
-        // For now, allow anyone to verify for demo purposes
-        
         let mut credential: Credential = env.storage().instance()
             .get(&DataKey::Credential(credential_id))
             .unwrap_or_else(|| panic!("Credential not found"));
 
+        if verifier != admin && verifier != credential.issuer {
+            panic!("Only admin or issuer can verify credentials");
+        }
+
         credential.is_verified = true;
         env.storage().instance().set(&DataKey::Credential(credential_id), &credential);
 
@@ -149,7 +518,7 @@ impl StarkEdContract {
             panic!("Only admin can create courses");
         }
 
-        let course_id = format!("course_{}", env.ledger().timestamp());
+        let course_id = numbered_id(&env, "course_", env.ledger().timestamp());
         let course = Course {
             id: course_id.clone(),
             instructor: instructor.clone(),
@@ -177,10 +546,971 @@ impl StarkEdContract {
             })
     }
 
+    /// Endorse a credential on behalf of a third party
+    pub fn endorse_credential(
+        env: Env,
+        endorser: Address,
+        credential_id: u64,
+        claim_hash: BytesN<32>,
+        weight: Option<u32>,
+    ) -> u64 {
+        endorser.require_auth();
+
+        let credential: Credential = env.storage().instance()
+            .get(&DataKey::Credential(credential_id))
+            .unwrap_or_else(|| panic!("Credential not found"));
+
+        if Self::credential_holder(env.clone(), &credential) == endorser {
+            panic!("Recipient cannot endorse own credential");
+        }
+
+        let weight = weight.unwrap_or(1);
+        if weight == 0 || weight > MAX_ENDORSEMENT_WEIGHT {
+            panic!("Endorsement weight out of range");
+        }
+
+        let mut endorsement_ids: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::CredentialEndorsements(credential_id))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        for existing_id in endorsement_ids.iter() {
+            let existing: Endorsement = env.storage().persistent()
+                .get(&DataKey::Endorsement(existing_id))
+                .unwrap();
+            if existing.endorser == endorser {
+                panic!("Credential already endorsed by this address");
+            }
+        }
+
+        let count: u64 = env.storage().instance()
+            .get(&DataKey::EndorsementCount)
+            .unwrap_or(0);
+        let endorsement_id = count + 1;
+
+        let endorsement = Endorsement {
+            id: endorsement_id,
+            credential_id,
+            endorser,
+            claim_hash,
+            weight,
+            applied_weight: 0,
+            status: EndorsementStatus::Pending,
+            created_at: env.ledger().timestamp(),
+        };
+
+        let endorser_key = DataKey::EndorserEndorsements(endorsement.endorser.clone());
+        let mut endorser_endorsements: Vec<u64> = env.storage().persistent()
+            .get(&endorser_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        endorser_endorsements.push_back(endorsement_id);
+        env.storage().persistent().set(&endorser_key, &endorser_endorsements);
+
+        endorsement_ids.push_back(endorsement_id);
+        env.storage().persistent().set(&DataKey::Endorsement(endorsement_id), &endorsement);
+        env.storage().instance().set(&DataKey::EndorsementCount, &endorsement_id);
+        env.storage().persistent().set(&DataKey::CredentialEndorsements(credential_id), &endorsement_ids);
+
+        endorsement_id
+    }
+
+    /// Accept a pending endorsement, adding its weight to the recipient's reputation if the endorser is approved
+    pub fn accept_endorsement(env: Env, endorsement_id: u64) {
+        let (mut endorsement, recipient) = Self::load_endorsement_for_recipient(env.clone(), endorsement_id);
+
+        if endorsement.status != EndorsementStatus::Pending {
+            panic!("Endorsement is not pending");
+        }
+
+        // Anyone can endorse, but only approved endorsers move reputation
+        if Self::is_endorser(env.clone(), endorsement.endorser.clone()) {
+            endorsement.applied_weight = endorsement.weight;
+
+            let mut profile = Self::get_profile(env.clone(), recipient.clone());
+            profile.reputation += endorsement.applied_weight as u64;
+            env.storage().instance().set(&recipient, &profile);
+        }
+
+        endorsement.status = EndorsementStatus::Accepted;
+        env.storage().persistent().set(&DataKey::Endorsement(endorsement_id), &endorsement);
+    }
+
+    /// Hide an endorsement, withdrawing any reputation it contributed
+    pub fn hide_endorsement(env: Env, endorsement_id: u64) {
+        let (mut endorsement, recipient) = Self::load_endorsement_for_recipient(env.clone(), endorsement_id);
+
+        if endorsement.status == EndorsementStatus::Hidden {
+            panic!("Endorsement already hidden");
+        }
+
+        Self::withdraw_endorsement_weight(env.clone(), &mut endorsement, recipient);
+
+        endorsement.status = EndorsementStatus::Hidden;
+        env.storage().persistent().set(&DataKey::Endorsement(endorsement_id), &endorsement);
+    }
+
+    /// Get endorsement details
+    pub fn get_endorsement(env: Env, endorsement_id: u64) -> Endorsement {
+        env.storage().persistent()
+            .get(&DataKey::Endorsement(endorsement_id))
+            .unwrap_or_else(|| panic!("Endorsement not found"))
+    }
+
+    /// Get the endorsements of a credential, optionally including hidden ones
+    pub fn get_credential_endorsements(env: Env, credential_id: u64, include_hidden: bool) -> Vec<Endorsement> {
+        let endorsement_ids: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::CredentialEndorsements(credential_id))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut endorsements = Vec::new(&env);
+        for endorsement_id in endorsement_ids.iter() {
+            let endorsement = Self::get_endorsement(env.clone(), endorsement_id);
+            if include_hidden || endorsement.status != EndorsementStatus::Hidden {
+                endorsements.push_back(endorsement);
+            }
+        }
+
+        endorsements
+    }
+
+    /// Get the ids of all credentials a recipient holds, following DID subjects to their current controller
+    pub fn get_recipient_credentials(env: Env, recipient: Address) -> Vec<u64> {
+        let issued: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::RecipientCredentials(recipient.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        // Drop DID credentials whose subject has since moved to another wallet
+        let mut credentials = Vec::new(&env);
+        for credential_id in issued.iter() {
+            let credential = Self::get_credential(env.clone(), credential_id);
+            if credential.subject_did.is_none() || Self::credential_holder(env.clone(), &credential) == recipient {
+                credentials.push_back(credential_id);
+            }
+        }
+
+        // Pick up credentials issued to a DID this address now controls
+        if let Some(registry) = env.storage().instance().get::<_, Address>(&DataKey::DidRegistryContract) {
+            let registry = DidRegistryContractClient::new(&env, &registry);
+            if let Some(did) = registry.get_did_for_address(&recipient) {
+                if registry.resolve(&did).controller == recipient {
+                    for credential_id in Self::get_did_credentials(env.clone(), did).iter() {
+                        if !credentials.contains(credential_id) {
+                            credentials.push_back(credential_id);
+                        }
+                    }
+                }
+            }
+        }
+
+        credentials
+    }
+
+    /// Point the contract at the CourseMetadataContract used for completions (Admin only)
+    pub fn set_course_metadata_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&DataKey::CourseMetadataContract, &contract);
+    }
+
+    /// Compile a transcript from a learner's verified credentials and completions
+    pub fn compile_transcript(env: Env, learner: Address) -> u64 {
+        learner.require_auth();
+
+        let mut entries: Vec<TranscriptEntry> = Vec::new(&env);
+
+        for credential_id in Self::get_recipient_credentials(env.clone(), learner.clone()).iter() {
+            let credential = Self::get_credential(env.clone(), credential_id);
+            if !credential.is_verified
+                || credential.superseded_by.is_some()
+                || Self::is_credential_expired(env.clone(), credential_id)
+            {
+                continue;
+            }
+
+            Self::insert_transcript_entry(&mut entries, TranscriptEntry {
+                title: credential.title,
+                course_id: credential.course_id,
+                grade: None,
+                completed_at: credential.completion_date,
+                credits: credential.credits,
+                credit_system: credential.credit_system,
+                credential_id: Some(credential_id),
+                completion_id: credential.completion_id,
+            });
+        }
+
+        if let Some(course_contract) = env.storage().instance().get::<_, Address>(&DataKey::CourseMetadataContract) {
+            let courses = CourseMetadataContractClient::new(&env, &course_contract);
+
+            for completion_id in courses.get_student_completions(&learner).iter() {
+                let completion = courses.get_completion(&completion_id);
+                if !completion.is_verified {
+                    continue;
+                }
+
+                // Merge into the entry of the credential issued for this completion when there is one
+                let mut merged = false;
+                for i in 0..entries.len() {
+                    let mut entry = entries.get(i).unwrap();
+                    if entry.completion_id == Some(completion_id.clone()) {
+                        entry.grade = Some(completion.final_grade);
+                        entries.set(i, entry);
+                        merged = true;
+                        break;
+                    }
+                }
+
+                if !merged {
+                    let course = courses.get_course(&completion.course_id);
+                    Self::insert_transcript_entry(&mut entries, TranscriptEntry {
+                        title: course.title,
+                        course_id: completion.course_id,
+                        grade: Some(completion.final_grade),
+                        completed_at: completion.completion_date,
+                        credits: completion.credits,
+                        credit_system: completion.credit_system,
+                        credential_id: None,
+                        completion_id: Some(completion_id),
+                    });
+                }
+            }
+        }
+
+        let count: u64 = env.storage().instance()
+            .get(&DataKey::TranscriptCount)
+            .unwrap_or(0);
+        let transcript_id = count + 1;
+
+        let transcript = Transcript {
+            id: transcript_id,
+            learner: learner.clone(),
+            digest: Self::transcript_digest(env.clone(), entries.clone()),
+            entries,
+            compiled_at: env.ledger().timestamp(),
+            sealed_by: None,
+            sealed_at: None,
+        };
+
+        let mut learner_transcripts: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::LearnerTranscripts(learner.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        learner_transcripts.push_back(transcript_id);
+
+        env.storage().persistent().set(&DataKey::Transcript(transcript_id), &transcript);
+        env.storage().instance().set(&DataKey::TranscriptCount, &transcript_id);
+        env.storage().persistent().set(&DataKey::LearnerTranscripts(learner), &learner_transcripts);
+
+        transcript_id
+    }
+
+    /// Seal a compiled transcript so it can be verified by third parties
+    pub fn seal_transcript(env: Env, issuer: Address, transcript_id: u64) {
+        Self::require_admin(env.clone(), issuer.clone());
+
+        let mut transcript = Self::get_transcript(env.clone(), transcript_id);
+        if transcript.sealed_by.is_some() {
+            panic!("Transcript already sealed");
+        }
+
+        transcript.sealed_by = Some(issuer);
+        transcript.sealed_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&DataKey::Transcript(transcript_id), &transcript);
+    }
+
+    /// Get transcript details
+    pub fn get_transcript(env: Env, transcript_id: u64) -> Transcript {
+        env.storage().persistent()
+            .get(&DataKey::Transcript(transcript_id))
+            .unwrap_or_else(|| panic!("Transcript not found"))
+    }
+
+    /// Get the ids of all transcripts compiled for a learner
+    pub fn get_learner_transcripts(env: Env, learner: Address) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&DataKey::LearnerTranscripts(learner))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Check that a transcript is sealed and matches the given digest
+    pub fn verify_transcript(env: Env, transcript_id: u64, digest: BytesN<32>) -> bool {
+        let transcript = Self::get_transcript(env.clone(), transcript_id);
+
+        transcript.sealed_by.is_some()
+            && transcript.digest == digest
+            && Self::transcript_digest(env, transcript.entries) == digest
+    }
+
+    /// Create a credential template for a course (Admin only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_template(
+        env: Env,
+        issuer: Address,
+        course_id: String,
+        title_pattern: String,
+        description: String,
+        design_cid: String,
+        validity_period: Option<u64>,
+        min_grade: u32,
+    ) -> u64 {
+        Self::require_admin(env.clone(), issuer.clone());
+
+        if min_grade > 100 {
+            panic!("Minimum grade must be between 0 and 100");
+        }
+        if title_pattern.len() as usize > MAX_TITLE_LEN {
+            panic!("Title pattern too long");
+        }
+
+        let count: u64 = env.storage().instance()
+            .get(&DataKey::TemplateCount)
+            .unwrap_or(0);
+        let template_id = count + 1;
+
+        let template = CredentialTemplate {
+            id: template_id,
+            course_id: course_id.clone(),
+            issuer,
+            title_pattern,
+            description,
+            design_cid,
+            validity_period,
+            min_grade,
+            is_active: true,
+            created_at: env.ledger().timestamp(),
+        };
+
+        let mut course_templates: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::CourseTemplates(course_id.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        course_templates.push_back(template_id);
+
+        env.storage().persistent().set(&DataKey::Template(template_id), &template);
+        env.storage().instance().set(&DataKey::TemplateCount, &template_id);
+        env.storage().persistent().set(&DataKey::CourseTemplates(course_id), &course_templates);
+
+        template_id
+    }
+
+    /// Deactivate a credential template so it can no longer be issued from (Admin only)
+    pub fn deactivate_template(env: Env, issuer: Address, template_id: u64) {
+        Self::require_admin(env.clone(), issuer);
+
+        let mut template = Self::get_template(env.clone(), template_id);
+        template.is_active = false;
+        env.storage().persistent().set(&DataKey::Template(template_id), &template);
+    }
+
+    /// Get credential template details
+    pub fn get_template(env: Env, template_id: u64) -> CredentialTemplate {
+        env.storage().persistent()
+            .get(&DataKey::Template(template_id))
+            .unwrap_or_else(|| panic!("Template not found"))
+    }
+
+    /// Get the ids of all templates defined for a course
+    pub fn get_course_templates(env: Env, course_id: String) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&DataKey::CourseTemplates(course_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Issue a credential from a template for a learner's course completion
+    pub fn issue_from_template(
+        env: Env,
+        issuer: Address,
+        template_id: u64,
+        recipient: Address,
+        completion_id: String,
+    ) -> u64 {
+        Self::require_admin(env.clone(), issuer.clone());
+
+        let template = Self::get_template(env.clone(), template_id);
+        if !template.is_active {
+            panic!("Template is not active");
+        }
+
+        let courses = Self::course_metadata_client(env.clone());
+
+        // Check the learner meets the template's criteria
+        let completion = courses.get_completion(&completion_id);
+        if completion.student != recipient {
+            panic!("Completion belongs to another student");
+        }
+        if completion.course_id != template.course_id {
+            panic!("Completion is for a different course");
+        }
+        if completion.final_grade < template.min_grade {
+            panic!("Grade below template minimum");
+        }
+        if !completion.is_verified {
+            panic!("Completion is not verified");
+        }
+        if env.storage().persistent().has(&DataKey::CompletionCredential(completion_id.clone())) {
+            panic!("Credential already issued for completion");
+        }
+        Self::require_proctor_clearance(env.clone(), template.course_id.clone(), recipient.clone());
+
+        let course = courses.get_course(&template.course_id);
+        let title = Self::render_title(env.clone(), template.title_pattern, course.title, completion.final_grade);
+        let expires_at = template.validity_period.map(|period| env.ledger().timestamp().saturating_add(period));
+
+        let credential_id = Self::store_credential(
+            env.clone(),
+            issuer,
+            recipient,
+            title,
+            template.description,
+            template.course_id,
+            template.design_cid,
+            expires_at,
+            Some(template_id),
+            None,
+        );
+
+        let mut credential = Self::get_credential(env.clone(), credential_id);
+        credential.completion_id = Some(completion_id.clone());
+        env.storage().instance().set(&DataKey::Credential(credential_id), &credential);
+        env.storage().persistent().set(&DataKey::CompletionCredential(completion_id), &credential_id);
+
+        credential_id
+    }
+
+    /// Check whether a credential has passed its expiry date
+    pub fn is_credential_expired(env: Env, credential_id: u64) -> bool {
+        let credential = Self::get_credential(env.clone(), credential_id);
+
+        match credential.expires_at {
+            Some(expires_at) => env.ledger().timestamp() >= expires_at,
+            None => false,
+        }
+    }
+
+    /// Approve an endorser whose accepted endorsements carry reputation weight (Admin only)
+    pub fn add_endorser(env: Env, admin: Address, endorser: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().set(&DataKey::Endorser(endorser), &true);
+    }
+
+    /// Withdraw an endorser's approval (Admin only)
+    pub fn remove_endorser(env: Env, admin: Address, endorser: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().remove(&DataKey::Endorser(endorser.clone()));
+
+        // Reputation the endorser granted goes with their approval
+        let endorsement_ids: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::EndorserEndorsements(endorser))
+            .unwrap_or_else(|| Vec::new(&env));
+        for endorsement_id in endorsement_ids.iter() {
+            let mut endorsement = Self::get_endorsement(env.clone(), endorsement_id);
+            if endorsement.applied_weight > 0 {
+                let credential = Self::get_credential(env.clone(), endorsement.credential_id);
+                let holder = Self::credential_holder(env.clone(), &credential);
+                Self::withdraw_endorsement_weight(env.clone(), &mut endorsement, holder);
+                env.storage().persistent().set(&DataKey::Endorsement(endorsement_id), &endorsement);
+            }
+        }
+    }
+
+    /// Check whether an address is an approved endorser
+    pub fn is_endorser(env: Env, address: Address) -> bool {
+        env.storage().persistent()
+            .get(&DataKey::Endorser(address))
+            .unwrap_or(false)
+    }
+
+    /// Register an arbitrator who can decide credential disputes (Admin only)
+    pub fn add_arbitrator(env: Env, admin: Address, arbitrator: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().set(&DataKey::Arbitrator(arbitrator), &true);
+    }
+
+    /// Remove a dispute arbitrator (Admin only)
+    pub fn remove_arbitrator(env: Env, admin: Address, arbitrator: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().remove(&DataKey::Arbitrator(arbitrator));
+    }
+
+    /// Check whether an address is a registered arbitrator
+    pub fn is_arbitrator(env: Env, address: Address) -> bool {
+        env.storage().persistent()
+            .get(&DataKey::Arbitrator(address))
+            .unwrap_or(false)
+    }
+
+    /// Open a dispute on a credential or course completion
+    pub fn open_dispute(
+        env: Env,
+        learner: Address,
+        subject: DisputeSubject,
+        reason: String,
+        evidence_hash: BytesN<32>,
+    ) -> u64 {
+        learner.require_auth();
+
+        match &subject {
+            DisputeSubject::Credential(credential_id) => {
+                let credential = Self::get_credential(env.clone(), *credential_id);
+                if Self::credential_holder(env.clone(), &credential) != learner {
+                    panic!("Only the credential recipient can dispute it");
+                }
+                if credential.superseded_by.is_some() {
+                    panic!("Credential has been superseded");
+                }
+            }
+            DisputeSubject::Completion(completion_id) => {
+                let completion = Self::course_metadata_client(env.clone()).get_completion(completion_id);
+                if completion.student != learner {
+                    panic!("Only the student can dispute a completion");
+                }
+            }
+        }
+
+        let open_key = DataKey::OpenDispute(subject.clone());
+        if env.storage().persistent().has(&open_key) {
+            panic!("Subject already has an open dispute");
+        }
+
+        let count: u64 = env.storage().instance()
+            .get(&DataKey::DisputeCount)
+            .unwrap_or(0);
+        let dispute_id = count + 1;
+
+        let dispute = Dispute {
+            id: dispute_id,
+            subject,
+            learner: learner.clone(),
+            reason,
+            evidence_hash: evidence_hash.clone(),
+            status: DisputeStatus::Open,
+            outcome: DisputeOutcome::Pending,
+            arbitrator: None,
+            replacement_credential_id: None,
+            opened_at: env.ledger().timestamp(),
+            resolved_at: None,
+        };
+
+        let mut learner_disputes: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::LearnerDisputes(learner.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        learner_disputes.push_back(dispute_id);
+
+        env.storage().persistent().set(&DataKey::Dispute(dispute_id), &dispute);
+        env.storage().instance().set(&DataKey::DisputeCount, &dispute_id);
+        env.storage().persistent().set(&DataKey::LearnerDisputes(learner.clone()), &learner_disputes);
+        env.storage().persistent().set(&open_key, &dispute_id);
+
+        Self::log_dispute_action(env, dispute_id, DisputeAction::Opened, learner, Some(evidence_hash));
+
+        dispute_id
+    }
+
+    /// Record the issuer's response to an open dispute
+    pub fn respond_to_dispute(env: Env, issuer: Address, dispute_id: u64, response_hash: BytesN<32>) {
+        Self::require_admin(env.clone(), issuer.clone());
+
+        let mut dispute = Self::get_dispute(env.clone(), dispute_id);
+        if dispute.status != DisputeStatus::Open {
+            panic!("Dispute is not open");
+        }
+
+        dispute.status = DisputeStatus::Responded;
+        env.storage().persistent().set(&DataKey::Dispute(dispute_id), &dispute);
+        env.storage().persistent().set(&DataKey::DisputeResponse(dispute_id), &response_hash);
+
+        Self::log_dispute_action(env, dispute_id, DisputeAction::Responded, issuer, Some(response_hash));
+    }
+
+    /// Decide a dispute, amending the credential or the completion's grade, or reissuing the credential
+    pub fn resolve_dispute(
+        env: Env,
+        arbitrator: Address,
+        dispute_id: u64,
+        outcome: DisputeOutcome,
+        title: Option<String>,
+        description: Option<String>,
+        grade: Option<u32>,
+    ) -> Option<u64> {
+        arbitrator.require_auth();
+        if !Self::is_arbitrator(env.clone(), arbitrator.clone()) {
+            panic!("Only an arbitrator can resolve disputes");
+        }
+
+        let mut dispute = Self::get_dispute(env.clone(), dispute_id);
+        if dispute.status == DisputeStatus::Resolved {
+            panic!("Dispute already resolved");
+        }
+
+        let replacement_id = match (&outcome, &dispute.subject) {
+            (DisputeOutcome::Pending, _) => panic!("Dispute outcome required"),
+            (DisputeOutcome::Rejected, _) => None,
+            (DisputeOutcome::Amended, DisputeSubject::Credential(credential_id)) => {
+                let mut credential = Self::get_credential(env.clone(), *credential_id);
+                if credential.superseded_by.is_some() {
+                    panic!("Credential has been superseded");
+                }
+                if let Some(new_title) = title {
+                    credential.title = new_title;
+                }
+                if let Some(new_description) = description {
+                    credential.description = new_description;
+                }
+                env.storage().instance().set(&DataKey::Credential(*credential_id), &credential);
+                None
+            }
+            (DisputeOutcome::Amended, DisputeSubject::Completion(completion_id)) => {
+                let grade = grade.unwrap_or_else(|| panic!("Amended grade required"));
+                Self::course_metadata_client(env.clone()).amend_completion_grade(completion_id, &grade);
+                None
+            }
+            (DisputeOutcome::Reissued, DisputeSubject::Credential(credential_id)) => {
+                let mut credential = Self::get_credential(env.clone(), *credential_id);
+                if credential.superseded_by.is_some() {
+                    panic!("Credential has been superseded");
+                }
+                let replacement_id = Self::store_credential(
+                    env.clone(),
+                    credential.issuer.clone(),
+                    Self::credential_holder(env.clone(), &credential),
+                    title.unwrap_or(credential.title.clone()),
+                    description.unwrap_or(credential.description.clone()),
+                    credential.course_id.clone(),
+                    credential.ipfs_hash.clone(),
+                    credential.expires_at,
+                    credential.template_id,
+                    credential.subject_did.clone(),
+                );
+
+                // The replacement stands in for the original wherever the original was looked up
+                let mut replacement = Self::get_credential(env.clone(), replacement_id);
+                replacement.completion_id = credential.completion_id.clone();
+                replacement.path_id = credential.path_id;
+                env.storage().instance().set(&DataKey::Credential(replacement_id), &replacement);
+                if let Some(completion_id) = credential.completion_id.clone() {
+                    Self::set_completion_credential(env.clone(), completion_id, replacement_id);
+                }
+                if let Some(path_id) = credential.path_id {
+                    env.storage().persistent().set(&DataKey::PathCredential(path_id, credential.recipient.clone()), &replacement_id);
+                }
+
+                credential.superseded_by = Some(replacement_id);
+                env.storage().instance().set(&DataKey::Credential(*credential_id), &credential);
+                Some(replacement_id)
+            }
+            (DisputeOutcome::Reissued, DisputeSubject::Completion(completion_id)) => {
+                let courses = Self::course_metadata_client(env.clone());
+                let completion = courses.get_completion(completion_id);
+                if !completion.is_verified {
+                    panic!("Completion is not verified");
+                }
+                let course = courses.get_course(&completion.course_id);
+                let admin: Address = env.storage().instance()
+                    .get(&DataKey::Admin)
+                    .unwrap_or_else(|| panic!("Admin not found"));
+
+                let replacement_id = Self::store_credential(
+                    env.clone(),
+                    admin,
+                    completion.student,
+                    title.unwrap_or(course.title),
+                    description.unwrap_or(course.description),
+                    completion.course_id,
+                    completion.certificate_hash,
+                    None,
+                    None,
+                    None,
+                );
+
+                let mut replacement = Self::get_credential(env.clone(), replacement_id);
+                replacement.completion_id = Some(completion_id.clone());
+                env.storage().instance().set(&DataKey::Credential(replacement_id), &replacement);
+
+                // A credential already issued for the completion is superseded rather than left standing beside the new one
+                if let Some(previous_id) = Self::get_completion_credential(env.clone(), completion_id.clone()) {
+                    let mut previous = Self::get_credential(env.clone(), previous_id);
+                    previous.superseded_by = Some(replacement_id);
+                    env.storage().instance().set(&DataKey::Credential(previous_id), &previous);
+                }
+                Self::set_completion_credential(env.clone(), completion_id.clone(), replacement_id);
+
+                Some(replacement_id)
+            }
+        };
+
+        dispute.status = DisputeStatus::Resolved;
+        dispute.outcome = outcome;
+        dispute.arbitrator = Some(arbitrator.clone());
+        dispute.replacement_credential_id = replacement_id;
+        dispute.resolved_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&DataKey::Dispute(dispute_id), &dispute);
+        env.storage().persistent().remove(&DataKey::OpenDispute(dispute.subject));
+
+        Self::log_dispute_action(env, dispute_id, DisputeAction::Resolved, arbitrator, None);
+
+        replacement_id
+    }
+
+    /// Get dispute details
+    pub fn get_dispute(env: Env, dispute_id: u64) -> Dispute {
+        env.storage().persistent()
+            .get(&DataKey::Dispute(dispute_id))
+            .unwrap_or_else(|| panic!("Dispute not found"))
+    }
+
+    /// Get the audit log of a dispute
+    pub fn get_dispute_log(env: Env, dispute_id: u64) -> Vec<DisputeLogEntry> {
+        env.storage().persistent()
+            .get(&DataKey::DisputeLog(dispute_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the issuer's response to a dispute, if one has been recorded
+    pub fn get_dispute_response(env: Env, dispute_id: u64) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::DisputeResponse(dispute_id))
+    }
+
+    /// Get the ids of all disputes opened by a learner
+    pub fn get_learner_disputes(env: Env, learner: Address) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&DataKey::LearnerDisputes(learner))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
     /// Get total credential count
     pub fn get_credential_count(env: Env) -> u64 {
         env.storage().instance()
             .get(&DataKey::CredentialCount)
             .unwrap_or(0)
     }
+
+    /// Load an endorsement and require authorization from the credential recipient
+    fn load_endorsement_for_recipient(env: Env, endorsement_id: u64) -> (Endorsement, Address) {
+        let endorsement = Self::get_endorsement(env.clone(), endorsement_id);
+        let credential = Self::get_credential(env.clone(), endorsement.credential_id);
+        let holder = Self::credential_holder(env, &credential);
+        holder.require_auth();
+
+        (endorsement, holder)
+    }
+
+    /// Take an endorsement's applied weight back out of the holder's reputation
+    fn withdraw_endorsement_weight(env: Env, endorsement: &mut Endorsement, holder: Address) {
+        if endorsement.applied_weight == 0 {
+            return;
+        }
+
+        let mut profile = Self::get_profile(env.clone(), holder.clone());
+        profile.reputation = profile.reputation.saturating_sub(endorsement.applied_weight as u64);
+        env.storage().instance().set(&holder, &profile);
+        endorsement.applied_weight = 0;
+    }
+
+    /// The address currently holding a credential: the DID's controller for DID subjects, else the recipient
+    fn credential_holder(env: Env, credential: &Credential) -> Address {
+        match (&credential.subject_did, env.storage().instance().get::<_, Address>(&DataKey::DidRegistryContract)) {
+            (Some(did), Some(registry)) => DidRegistryContractClient::new(&env, &registry).resolve(did).controller,
+            _ => credential.recipient.clone(),
+        }
+    }
+
+    /// Require a clean or cleared exam session before issuing for a completion of a proctored course
+    fn require_proctor_clearance(env: Env, course_id: String, recipient: Address) {
+        if let Some(proctor_registry) = env.storage().instance().get::<_, Address>(&DataKey::ProctorRegistryContract) {
+            match ProctorRegistryContractClient::new(&env, &proctor_registry).get_clearance(&course_id, &recipient) {
+                ProctorClearance::NotRequired | ProctorClearance::Cleared => {}
+                ProctorClearance::Missing => panic!("Proctored exam attestation required"),
+                ProctorClearance::UnderReview => panic!("Proctored session is under review"),
+                ProctorClearance::Rejected => panic!("Proctored session was rejected"),
+            }
+        }
+    }
+
+    /// Require authorization from the admin address
+    fn require_admin(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Admin not found"));
+
+        if caller != admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+
+    /// Insert an entry keeping transcripts in canonical (date, course id) order
+    fn insert_transcript_entry(entries: &mut Vec<TranscriptEntry>, entry: TranscriptEntry) {
+        let mut index = entries.len();
+        for (i, existing) in entries.iter().enumerate() {
+            if (entry.completed_at, &entry.course_id) < (existing.completed_at, &existing.course_id) {
+                index = i as u32;
+                break;
+            }
+        }
+        entries.insert(index, entry);
+    }
+
+    /// SHA-256 digest over the canonical XDR encoding of transcript entries
+    fn transcript_digest(env: Env, entries: Vec<TranscriptEntry>) -> BytesN<32> {
+        env.crypto().sha256(&entries.to_xdr(&env)).into()
+    }
+
+    /// Store a new credential and index it under its recipient
+    #[allow(clippy::too_many_arguments)]
+    fn store_credential(
+        env: Env,
+        issuer: Address,
+        recipient: Address,
+        title: String,
+        description: String,
+        course_id: String,
+        ipfs_hash: String,
+        expires_at: Option<u64>,
+        template_id: Option<u64>,
+        subject_did: Option<String>,
+    ) -> u64 {
+        let count: u64 = env.storage().instance()
+            .get(&DataKey::CredentialCount)
+            .unwrap_or(0);
+        let credential_id = count + 1;
+
+        // Carry the course's credit value onto the credential when the course is known
+        let mut credits = 0;
+        let mut credit_system = CreditSystem::None;
+        if let Some(course_contract) = env.storage().instance().get::<_, Address>(&DataKey::CourseMetadataContract) {
+            let courses = CourseMetadataContractClient::new(&env, &course_contract);
+            if let Ok(Ok(course)) = courses.try_get_course(&course_id) {
+                credits = course.credit_value;
+                credit_system = course.credit_system;
+            }
+        }
+
+        let credential = Credential {
+            id: credential_id,
+            issuer: issuer.clone(),
+            recipient: recipient.clone(),
+            title,
+            description,
+            course_id,
+            completion_date: env.ledger().timestamp(),
+            ipfs_hash,
+            is_verified: false,
+            credits,
+            credit_system,
+            expires_at,
+            template_id,
+            superseded_by: None,
+            subject_did: subject_did.clone(),
+            completion_id: None,
+            path_id: None,
+        };
+
+        env.storage().instance().set(&DataKey::Credential(credential_id), &credential);
+        env.storage().instance().set(&DataKey::CredentialCount, &credential_id);
+
+        let mut recipient_credentials: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::RecipientCredentials(recipient.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        recipient_credentials.push_back(credential_id);
+        env.storage().persistent().set(&DataKey::RecipientCredentials(recipient), &recipient_credentials);
+
+        if let Some(did) = subject_did {
+            let mut did_credentials: Vec<u64> = env.storage().persistent()
+                .get(&DataKey::DidCredentials(did.clone()))
+                .unwrap_or_else(|| Vec::new(&env));
+            did_credentials.push_back(credential_id);
+            env.storage().persistent().set(&DataKey::DidCredentials(did), &did_credentials);
+        }
+
+        credential_id
+    }
+
+    /// Render a template title, substituting {course} and {grade}
+    fn render_title(env: Env, pattern: String, course_title: String, grade: u32) -> String {
+        let pattern_len = pattern.len() as usize;
+        let course_len = course_title.len() as usize;
+        if course_len > MAX_TITLE_LEN {
+            panic!("Course title too long");
+        }
+
+        let mut pattern_buf = [0u8; MAX_TITLE_LEN];
+        pattern.copy_into_slice(&mut pattern_buf[..pattern_len]);
+        let mut course_buf = [0u8; MAX_TITLE_LEN];
+        course_title.copy_into_slice(&mut course_buf[..course_len]);
+
+        let mut grade_buf = [0u8; 10];
+        let mut grade_start = grade_buf.len();
+        let mut remaining = grade;
+        loop {
+            grade_start -= 1;
+            grade_buf[grade_start] = b'0' + (remaining % 10) as u8;
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        let mut title_buf = [0u8; MAX_TITLE_LEN];
+        let mut title_len = 0;
+        let mut i = 0;
+        while i < pattern_len {
+            let rest = &pattern_buf[i..pattern_len];
+            let (piece, consumed): (&[u8], usize) = if rest.starts_with(b"{course}") {
+                (&course_buf[..course_len], 8)
+            } else if rest.starts_with(b"{grade}") {
+                (&grade_buf[grade_start..], 7)
+            } else {
+                (&pattern_buf[i..i + 1], 1)
+            };
+
+            if title_len + piece.len() > MAX_TITLE_LEN {
+                panic!("Rendered title too long");
+            }
+            title_buf[title_len..title_len + piece.len()].copy_from_slice(piece);
+            title_len += piece.len();
+            i += consumed;
+        }
+
+        String::from_bytes(&env, &title_buf[..title_len])
+    }
+
+    /// Client for the configured CourseMetadataContract
+    fn course_metadata_client(env: Env) -> CourseMetadataContractClient<'static> {
+        let course_contract: Address = env.storage().instance()
+            .get(&DataKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Course metadata contract not set"));
+
+        CourseMetadataContractClient::new(&env, &course_contract)
+    }
+
+    /// Record the credential issued for a completion, here and on the completion itself
+    fn set_completion_credential(env: Env, completion_id: String, credential_id: u64) {
+        env.storage().persistent().set(&DataKey::CompletionCredential(completion_id.clone()), &credential_id);
+        Self::course_metadata_client(env).set_completion_credential(&completion_id, &credential_id);
+    }
+
+    /// Append a step to a dispute's audit log and publish it as an event
+    fn log_dispute_action(
+        env: Env,
+        dispute_id: u64,
+        action: DisputeAction,
+        actor: Address,
+        document_hash: Option<BytesN<32>>,
+    ) {
+        let mut documents = Vec::new(&env);
+        if let Some(document_hash) = document_hash {
+            documents.push_back(document_hash);
+        }
+
+        let mut log = Self::get_dispute_log(env.clone(), dispute_id);
+        log.push_back(DisputeLogEntry {
+            action: action.clone(),
+            actor: actor.clone(),
+            documents,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&DataKey::DisputeLog(dispute_id), &log);
+
+        env.events().publish(
+            (symbol_short!("dispute"), action),
+            (dispute_id, actor)
+        );
+    }
 }
@@ -0,0 +1,601 @@
+#![cfg(test)]
+
+use crate::{DataKey, DisputeAction, DisputeOutcome, DisputeStatus, DisputeSubject, EndorsementStatus, StarkEdContract, StarkEdContractClient};
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, NewCourse};
+use crate::eventLogger::{EventLoggerContract, EventLoggerContractClient};
+use crate::proctorRegistry::{ProctorRegistryContract, ProctorRegistryContractClient};
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Address, BytesN, Env, String};
+
+fn setup(env: &Env) -> (StarkEdContractClient<'_>, Address) {
+    let contract_id = env.register_contract(None, StarkEdContract);
+    let client = StarkEdContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    (client, admin)
+}
+
+fn setup_courses<'a>(
+    env: &Env,
+    client: &StarkEdContractClient,
+    admin: &Address,
+) -> (CourseMetadataContractClient<'a>, String) {
+    let courses_id = env.register_contract(None, CourseMetadataContract);
+    let courses = CourseMetadataContractClient::new(env, &courses_id);
+    courses.initialize(admin);
+    client.set_course_metadata_contract(admin, &courses_id);
+
+    let course_id = courses.create_course(
+        &Address::generate(env),
+        &NewCourse {
+            title: String::from_str(env, "Soroban Basics"),
+            description: String::from_str(env, "Smart contracts on Stellar"),
+            category: String::from_str(env, "Programming"),
+            level: String::from_str(env, "beginner"),
+            duration: 10,
+            price: 0,
+            prerequisites: vec![env],
+            learning_objectives: vec![env],
+            syllabus: String::from_str(env, "QmSyllabus"),
+            thumbnail_url: String::from_str(env, "https://example.com/thumbnail.jpg"),
+            tags: vec![env],
+            language: String::from_str(env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+
+    (courses, course_id)
+}
+
+fn issue(env: &Env, client: &StarkEdContractClient, admin: &Address, recipient: &Address) -> u64 {
+    client.issue_credential(
+        admin,
+        recipient,
+        &String::from_str(env, "Rust Fundamentals"),
+        &String::from_str(env, "Completed the Rust fundamentals course"),
+        &String::from_str(env, "course_1"),
+        &String::from_str(env, "QmCertificateHash"),
+    )
+}
+
+#[test]
+fn test_endorsement_flow() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let employer = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &recipient);
+    client.add_endorser(&admin, &employer);
+
+    let endorsement_id = client.endorse_credential(
+        &employer,
+        &credential_id,
+        &BytesN::from_array(&env, &[7; 32]),
+        &Some(10),
+    );
+
+    let endorsement = client.get_endorsement(&endorsement_id);
+    assert_eq!(endorsement.status, EndorsementStatus::Pending);
+    assert_eq!(endorsement.weight, 10);
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+
+    // Accepting feeds the weight into reputation
+    client.accept_endorsement(&endorsement_id);
+    assert_eq!(client.get_endorsement(&endorsement_id).status, EndorsementStatus::Accepted);
+    assert_eq!(client.get_profile(&recipient).reputation, 10);
+
+    // Hiding withdraws it again, and a hidden endorsement cannot be re-accepted
+    client.hide_endorsement(&endorsement_id);
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+    assert_eq!(client.get_endorsement(&endorsement_id).weight, 10);
+    assert!(client.try_accept_endorsement(&endorsement_id).is_err());
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+    assert_eq!(client.get_credential_endorsements(&credential_id, &false).len(), 0);
+    assert_eq!(client.get_credential_endorsements(&credential_id, &true).len(), 1);
+
+    // Endorsements live in persistent storage, not the shared instance entry
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&DataKey::Endorsement(endorsement_id)));
+        assert!(!env.storage().instance().has(&DataKey::Endorsement(endorsement_id)));
+    });
+}
+
+#[test]
+fn test_unapproved_endorser_carries_no_weight() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &recipient);
+
+    let endorsement_id = client.endorse_credential(&stranger, &credential_id, &BytesN::from_array(&env, &[3; 32]), &Some(50));
+    client.accept_endorsement(&endorsement_id);
+
+    // Accepted and visible, but no reputation without admin approval
+    let endorsement = client.get_endorsement(&endorsement_id);
+    assert_eq!(endorsement.status, EndorsementStatus::Accepted);
+    assert_eq!(endorsement.weight, 50);
+    assert_eq!(endorsement.applied_weight, 0);
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+    assert!(!client.is_endorser(&stranger));
+
+    // Hiding it takes nothing away
+    client.hide_endorsement(&endorsement_id);
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+}
+
+#[test]
+fn test_removed_endorser_reputation_withdrawn() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let employer = Address::generate(&env);
+    client.add_endorser(&admin, &employer);
+
+    let first = issue(&env, &client, &admin, &recipient);
+    let second = issue(&env, &client, &admin, &recipient);
+    let accepted = client.endorse_credential(&employer, &first, &BytesN::from_array(&env, &[1; 32]), &Some(20));
+    let pending = client.endorse_credential(&employer, &second, &BytesN::from_array(&env, &[2; 32]), &Some(30));
+    client.accept_endorsement(&accepted);
+    assert_eq!(client.get_profile(&recipient).reputation, 20);
+
+    client.remove_endorser(&admin, &employer);
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+    assert_eq!(client.get_endorsement(&accepted).applied_weight, 0);
+
+    // Later acceptances carry nothing either
+    client.accept_endorsement(&pending);
+    assert_eq!(client.get_profile(&recipient).reputation, 0);
+}
+
+#[test]
+#[should_panic(expected = "Credential already endorsed by this address")]
+fn test_duplicate_endorsement_rejected() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let employer = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &recipient);
+    let claim_hash = BytesN::from_array(&env, &[1; 32]);
+
+    client.endorse_credential(&employer, &credential_id, &claim_hash, &None);
+    client.endorse_credential(&employer, &credential_id, &claim_hash, &None);
+}
+
+#[test]
+#[should_panic(expected = "Recipient cannot endorse own credential")]
+fn test_self_endorsement_rejected() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let recipient = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &recipient);
+
+    client.endorse_credential(&recipient, &credential_id, &BytesN::from_array(&env, &[1; 32]), &None);
+}
+
+#[test]
+fn test_transcript_compile_and_seal() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let learner = Address::generate(&env);
+
+    env.ledger().set_timestamp(200);
+    let later = issue(&env, &client, &admin, &learner);
+    env.ledger().set_timestamp(100);
+    let earlier = issue(&env, &client, &admin, &learner);
+    let unverified = issue(&env, &client, &admin, &learner);
+
+    client.verify_credential(&admin, &later);
+    client.verify_credential(&admin, &earlier);
+
+    let transcript_id = client.compile_transcript(&learner);
+    let transcript = client.get_transcript(&transcript_id);
+
+    // Only verified credentials, oldest first
+    assert_eq!(transcript.entries.len(), 2);
+    assert_eq!(transcript.entries.get(0).unwrap().credential_id, Some(earlier));
+    assert_eq!(transcript.entries.get(1).unwrap().credential_id, Some(later));
+    assert!(transcript.entries.iter().all(|entry| entry.credential_id != Some(unverified)));
+
+    // Not verifiable until sealed
+    assert!(!client.verify_transcript(&transcript_id, &transcript.digest));
+
+    client.seal_transcript(&admin, &transcript_id);
+    assert!(client.verify_transcript(&transcript_id, &transcript.digest));
+    assert!(!client.verify_transcript(&transcript_id, &BytesN::from_array(&env, &[0; 32])));
+    assert_eq!(client.get_learner_transcripts(&learner).len(), 1);
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&DataKey::Transcript(transcript_id)));
+        assert!(env.storage().persistent().has(&DataKey::LearnerTranscripts(learner.clone())));
+    });
+}
+
+#[test]
+fn test_transcript_merges_completions_by_credential() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    courses.set_credential_contract(&admin, &client.address);
+    let learner = Address::generate(&env);
+
+    // Two completions of the same course, the second one certified
+    env.ledger().set_timestamp(100);
+    let first = courses.record_completion(&course_id, &learner, &55, &String::from_str(&env, "QmFirst"), &vec![&env]);
+    env.ledger().set_timestamp(200);
+    let second = courses.record_completion(&course_id, &learner, &88, &String::from_str(&env, "QmSecond"), &vec![&env]);
+    courses.verify_completion(&second, &admin);
+    courses.set_credential_contract(&admin, &Address::generate(&env));
+    courses.verify_completion(&first, &admin);
+
+    let credential_id = client.get_completion_credential(&second).unwrap();
+    client.verify_credential(&admin, &credential_id);
+
+    // The credential carries the grade of its own completion, not the first one for the course
+    let transcript = client.get_transcript(&client.compile_transcript(&learner));
+    assert_eq!(transcript.entries.len(), 2);
+    let first_entry = transcript.entries.get(0).unwrap();
+    assert_eq!(first_entry.completion_id, Some(first));
+    assert_eq!(first_entry.credential_id, None);
+    assert_eq!(first_entry.grade, Some(55));
+    let second_entry = transcript.entries.get(1).unwrap();
+    assert_eq!(second_entry.credential_id, Some(credential_id));
+    assert_eq!(second_entry.grade, Some(88));
+}
+
+#[test]
+#[should_panic(expected = "Only admin or issuer can verify credentials")]
+fn test_verify_credential_requires_admin_or_issuer() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let learner = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &learner);
+
+    client.verify_credential(&learner, &credential_id);
+}
+
+#[test]
+#[should_panic(expected = "Only admin can perform this action")]
+fn test_transcript_seal_requires_admin() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let learner = Address::generate(&env);
+
+    let transcript_id = client.compile_transcript(&learner);
+    client.seal_transcript(&learner, &transcript_id);
+}
+
+#[test]
+fn test_issue_from_template() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    let learner = Address::generate(&env);
+
+    let template_id = client.create_template(
+        &admin,
+        &course_id,
+        &String::from_str(&env, "{course} Certificate ({grade}%)"),
+        &String::from_str(&env, "Awarded for completing the course"),
+        &String::from_str(&env, "QmDesign"),
+        &Some(1000),
+        &70,
+    );
+    assert_eq!(client.get_course_templates(&course_id).len(), 1);
+
+    env.ledger().set_timestamp(500);
+    let completion_id = courses.record_completion(
+        &course_id,
+        &learner,
+        &85,
+        &String::from_str(&env, "QmCertHash"),
+        &vec![&env],
+    );
+
+    // Unverified completions cannot be issued against
+    assert!(client.try_issue_from_template(&admin, &template_id, &learner, &completion_id).is_err());
+    courses.verify_completion(&completion_id, &admin);
+
+    let credential_id = client.issue_from_template(&admin, &template_id, &learner, &completion_id);
+    let credential = client.get_credential(&credential_id);
+    assert_eq!(credential.title, String::from_str(&env, "Soroban Basics Certificate (85%)"));
+    assert_eq!(credential.ipfs_hash, String::from_str(&env, "QmDesign"));
+    assert_eq!(credential.expires_at, Some(1500));
+    assert_eq!(credential.template_id, Some(template_id));
+    assert_eq!(credential.completion_id, Some(completion_id.clone()));
+    assert_eq!(client.get_completion_credential(&completion_id), Some(credential_id));
+
+    // One credential per completion
+    assert!(client.try_issue_from_template(&admin, &template_id, &learner, &completion_id).is_err());
+
+    client.verify_credential(&admin, &credential_id);
+    assert!(!client.is_credential_expired(&credential_id));
+    env.ledger().set_timestamp(1500);
+    assert!(client.is_credential_expired(&credential_id));
+
+    // Expired credentials drop out of transcripts; the verified completion still counts
+    let transcript = client.get_transcript(&client.compile_transcript(&learner));
+    assert_eq!(transcript.entries.len(), 1);
+    assert_eq!(transcript.entries.get(0).unwrap().credential_id, None);
+    assert_eq!(transcript.entries.get(0).unwrap().completion_id, Some(completion_id));
+}
+
+#[test]
+fn test_template_validity_saturates() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    let learner = Address::generate(&env);
+
+    let template_id = client.create_template(
+        &admin,
+        &course_id,
+        &String::from_str(&env, "{course}"),
+        &String::from_str(&env, "Awarded for completing the course"),
+        &String::from_str(&env, "QmDesign"),
+        &Some(u64::MAX),
+        &0,
+    );
+
+    env.ledger().set_timestamp(500);
+    let completion_id = courses.record_completion(&course_id, &learner, &85, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+    courses.verify_completion(&completion_id, &admin);
+
+    let credential_id = client.issue_from_template(&admin, &template_id, &learner, &completion_id);
+    assert_eq!(client.get_credential(&credential_id).expires_at, Some(u64::MAX));
+    assert!(!client.is_credential_expired(&credential_id));
+}
+
+#[test]
+#[should_panic(expected = "Grade below template minimum")]
+fn test_issue_from_template_requires_min_grade() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    let learner = Address::generate(&env);
+
+    let template_id = client.create_template(
+        &admin,
+        &course_id,
+        &String::from_str(&env, "{course}"),
+        &String::from_str(&env, "Awarded for completing the course"),
+        &String::from_str(&env, "QmDesign"),
+        &None,
+        &70,
+    );
+    let completion_id = courses.record_completion(
+        &course_id,
+        &learner,
+        &60,
+        &String::from_str(&env, "QmCertHash"),
+        &vec![&env],
+    );
+    courses.verify_completion(&completion_id, &admin);
+
+    client.issue_from_template(&admin, &template_id, &learner, &completion_id);
+}
+
+#[test]
+fn test_dispute_reissues_credential() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let learner = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &learner);
+
+    client.add_arbitrator(&admin, &arbitrator);
+
+    let dispute_id = client.open_dispute(
+        &learner,
+        &DisputeSubject::Credential(credential_id),
+        &String::from_str(&env, "Name misspelled"),
+        &BytesN::from_array(&env, &[3; 32]),
+    );
+    assert_eq!(client.get_dispute(&dispute_id).status, DisputeStatus::Open);
+
+    client.respond_to_dispute(&admin, &dispute_id, &BytesN::from_array(&env, &[4; 32]));
+    assert_eq!(client.get_dispute(&dispute_id).status, DisputeStatus::Responded);
+
+    let replacement_id = client.resolve_dispute(
+        &arbitrator,
+        &dispute_id,
+        &DisputeOutcome::Reissued,
+        &Some(String::from_str(&env, "Rust Fundamentals (corrected)")),
+        &None,
+        &None,
+    ).unwrap();
+
+    // Old credential points at its replacement
+    assert_eq!(client.get_credential(&credential_id).superseded_by, Some(replacement_id));
+    let replacement = client.get_credential(&replacement_id);
+    assert_eq!(replacement.recipient, learner);
+    assert_eq!(replacement.title, String::from_str(&env, "Rust Fundamentals (corrected)"));
+
+    let dispute = client.get_dispute(&dispute_id);
+    assert_eq!(dispute.status, DisputeStatus::Resolved);
+    assert_eq!(dispute.outcome, DisputeOutcome::Reissued);
+    assert_eq!(dispute.replacement_credential_id, Some(replacement_id));
+
+    // Every step is in the audit log
+    let log = client.get_dispute_log(&dispute_id);
+    assert_eq!(log.len(), 3);
+    assert_eq!(log.get(0).unwrap().action, DisputeAction::Opened);
+    assert_eq!(log.get(2).unwrap().actor, arbitrator);
+}
+
+#[test]
+fn test_dispute_reissue_replaces_completion_credential() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    courses.set_credential_contract(&admin, &client.address);
+    let learner = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    client.add_arbitrator(&admin, &arbitrator);
+
+    let completion_id = courses.record_completion(&course_id, &learner, &92, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+    courses.verify_completion(&completion_id, &admin);
+    let credential_id = client.get_completion_credential(&completion_id).unwrap();
+
+    let subject = DisputeSubject::Credential(credential_id);
+    let dispute_id = client.open_dispute(&learner, &subject, &String::from_str(&env, "Name misspelled"), &BytesN::from_array(&env, &[3; 32]));
+
+    // One open dispute per subject
+    assert!(client.try_open_dispute(&learner, &subject, &String::from_str(&env, "Again"), &BytesN::from_array(&env, &[4; 32])).is_err());
+
+    let replacement_id = client.resolve_dispute(&arbitrator, &dispute_id, &DisputeOutcome::Reissued, &None, &None, &None).unwrap();
+
+    // The replacement takes over the completion link on both sides
+    assert_eq!(client.get_credential(&replacement_id).completion_id, Some(completion_id.clone()));
+    assert_eq!(client.get_completion_credential(&completion_id), Some(replacement_id));
+    assert_eq!(courses.get_completion(&completion_id).credential_id, Some(replacement_id));
+
+    // The superseded credential cannot be disputed again, the replacement can
+    assert!(client.try_open_dispute(&learner, &subject, &String::from_str(&env, "Again"), &BytesN::from_array(&env, &[4; 32])).is_err());
+    client.open_dispute(&learner, &DisputeSubject::Credential(replacement_id), &String::from_str(&env, "Grade"), &BytesN::from_array(&env, &[5; 32]));
+}
+
+#[test]
+fn test_completion_dispute_amends_grade_and_reissues() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    courses.set_credential_contract(&admin, &client.address);
+    let learner = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    client.add_arbitrator(&admin, &arbitrator);
+
+    // A proctored course whose learner never sat an attested exam
+    let registry = ProctorRegistryContractClient::new(&env, &env.register_contract(None, ProctorRegistryContract));
+    registry.initialize(&admin);
+    registry.set_course_exam(&admin, &course_id, &String::from_str(&env, "final_exam"));
+    client.set_proctor_registry_contract(&admin, &registry.address);
+
+    let completion_id = courses.record_completion(&course_id, &learner, &60, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+    let subject = DisputeSubject::Completion(completion_id.clone());
+    let evidence = BytesN::from_array(&env, &[3; 32]);
+
+    let dispute_id = client.open_dispute(&learner, &subject, &String::from_str(&env, "Wrong grade"), &evidence);
+    assert!(client.try_resolve_dispute(&arbitrator, &dispute_id, &DisputeOutcome::Amended, &None, &None, &None).is_err());
+    client.resolve_dispute(&arbitrator, &dispute_id, &DisputeOutcome::Amended, &None, &None, &Some(75));
+    assert_eq!(courses.get_completion(&completion_id).final_grade, 75);
+
+    // Reissuing needs a verified completion
+    let dispute_id = client.open_dispute(&learner, &subject, &String::from_str(&env, "No certificate"), &evidence);
+    assert!(client.try_resolve_dispute(&arbitrator, &dispute_id, &DisputeOutcome::Reissued, &None, &None, &None).is_err());
+    courses.verify_completion(&completion_id, &admin);
+    assert_eq!(courses.get_completion(&completion_id).credential_id, None);
+
+    // The arbitrator's decision stands in for the proctor gate
+    let credential_id = client.resolve_dispute(&arbitrator, &dispute_id, &DisputeOutcome::Reissued, &None, &None, &None).unwrap();
+    assert_eq!(client.get_credential(&credential_id).completion_id, Some(completion_id.clone()));
+    assert_eq!(client.get_completion_credential(&completion_id), Some(credential_id));
+    assert_eq!(courses.get_completion(&completion_id).credential_id, Some(credential_id));
+
+    // A second reissue supersedes the first
+    let dispute_id = client.open_dispute(&learner, &subject, &String::from_str(&env, "Wrong title"), &evidence);
+    let replacement_id = client.resolve_dispute(&arbitrator, &dispute_id, &DisputeOutcome::Reissued, &None, &None, &None).unwrap();
+    assert_eq!(client.get_credential(&credential_id).superseded_by, Some(replacement_id));
+    assert_eq!(client.get_completion_credential(&completion_id), Some(replacement_id));
+}
+
+#[test]
+#[should_panic(expected = "Only an arbitrator can resolve disputes")]
+fn test_dispute_requires_arbitrator() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let learner = Address::generate(&env);
+    let credential_id = issue(&env, &client, &admin, &learner);
+
+    let dispute_id = client.open_dispute(
+        &learner,
+        &DisputeSubject::Credential(credential_id),
+        &String::from_str(&env, "Wrong grade"),
+        &BytesN::from_array(&env, &[3; 32]),
+    );
+    client.resolve_dispute(&admin, &dispute_id, &DisputeOutcome::Amended, &None, &None, &None);
+}
+
+#[test]
+fn test_verified_completion_issues_credential() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    let learner = Address::generate(&env);
+
+    let logger_id = env.register_contract(None, EventLoggerContract);
+    let logger = EventLoggerContractClient::new(&env, &logger_id);
+    logger.initialize(&admin);
+    logger.add_issuer(&admin, &courses.address);
+
+    courses.set_credential_contract(&admin, &client.address);
+    courses.set_event_logger_contract(&admin, &logger_id);
+
+    let completion_id = courses.record_completion(
+        &course_id,
+        &learner,
+        &92,
+        &String::from_str(&env, "QmCertHash"),
+        &vec![&env],
+    );
+    assert_eq!(client.get_completion_credential(&completion_id), None);
+
+    courses.verify_completion(&completion_id, &admin);
+
+    // Completion and credential point at each other
+    let credential_id = courses.get_completion(&completion_id).credential_id.unwrap();
+    assert_eq!(client.get_completion_credential(&completion_id), Some(credential_id));
+    let credential = client.get_credential(&credential_id);
+    assert_eq!(credential.recipient, learner);
+    assert_eq!(credential.title, String::from_str(&env, "Soroban Basics"));
+    assert_eq!(credential.completion_id, Some(completion_id));
+
+    // Issuance was logged
+    assert_eq!(logger.get_event_count(), 1);
+    assert_eq!(logger.get_user_events(&learner).get(0).unwrap().credential_id, Some(credential_id));
+}
+
+#[test]
+#[should_panic(expected = "Only admin or course instructor can verify completions")]
+fn test_verify_completion_requires_admin_or_instructor() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+
+    let learner = Address::generate(&env);
+    let completion_id = courses.record_completion(&course_id, &learner, &92, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+    courses.verify_completion(&completion_id, &learner);
+}
+
+#[test]
+fn test_proctor_gate_defers_completion_credential() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (courses, course_id) = setup_courses(&env, &client, &admin);
+    courses.set_credential_contract(&admin, &client.address);
+
+    let registry = ProctorRegistryContractClient::new(&env, &env.register_contract(None, ProctorRegistryContract));
+    registry.initialize(&admin);
+    let proctor = Address::generate(&env);
+    let exam_id = String::from_str(&env, "final_exam");
+    registry.add_proctor(&admin, &proctor);
+    registry.set_course_exam(&admin, &course_id, &exam_id);
+    client.set_proctor_registry_contract(&admin, &registry.address);
+
+    let learner = Address::generate(&env);
+    let completion_id = courses.record_completion(&course_id, &learner, &92, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+
+    // Verification goes through without an attested session; only the credential waits
+    courses.verify_completion(&completion_id, &admin);
+    let completion = courses.get_completion(&completion_id);
+    assert!(completion.is_verified);
+    assert_eq!(completion.credential_id, None);
+    assert!(courses.try_claim_completion_credential(&completion_id).is_err());
+
+    registry.attest_session(&proctor, &exam_id, &learner, &BytesN::from_array(&env, &[1; 32]), &true);
+    let credential_id = courses.claim_completion_credential(&completion_id);
+    assert_eq!(courses.get_completion(&completion_id).credential_id, Some(credential_id));
+    assert_eq!(client.get_completion_credential(&completion_id), Some(credential_id));
+}
@@ -0,0 +1,527 @@
+use soroban_sdk::{contract, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+use crate::courseMetadata::CourseMetadataContractClient;
+
+/// Upper bound on the number of rubric criteria per assignment
+pub const MAX_RUBRIC_CRITERIA: u32 = 20;
+
+/// Upper bound on the number of reviews each submission receives
+pub const MAX_REVIEWS_PER_SUBMISSION: u32 = 7;
+
+/// Upper bound on the shuffle and assignment steps one assign_reviewers call performs
+pub const MAX_ASSIGNMENT_BATCH: u32 = 50;
+
+/// Upper bound on the number of submitters returned per page
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RubricCriterion {
+    pub name: String,
+    pub max_points: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PeerAssignment {
+    pub id: u64,
+    pub course_id: String,
+    pub instructor: Address,
+    pub component_id: u32, // gradebook component the final peer scores are recorded against
+    pub rubric: Vec<RubricCriterion>,
+    pub reviews_per_submission: u32,
+    pub outlier_threshold: u32, // percent of the rubric maximum a review may stray from the median
+    pub submission_deadline: u64,
+    pub review_deadline: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PeerSubmission {
+    pub author: Address,
+    pub content_hash: BytesN<32>, // hash of the submitted work, stored off-chain
+    pub submitted_at: u64,
+    pub reviewers: Vec<Address>,
+    pub final_score: Option<u32>, // 0-100, median review as a percent of the rubric maximum
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PeerReview {
+    pub reviewer: Address,
+    pub scores: Vec<u32>, // points per rubric criterion
+    pub total: u32,
+    pub submitted_at: u64,
+    pub is_outlier: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewAssignmentProgress {
+    pub shuffled: u32, // ring positions fixed so far
+    pub assigned: u32, // ring positions whose reviewers have been assigned
+}
+
+#[contracttype]
+pub enum PeerReviewKey {
+    Assignment(u64),
+    AssignmentCount,
+    Submission(u64, Address),
+    Submitter(u64, u32), // submitters by submission order
+    SubmitterCount(u64),
+    ReviewOrder(u64, u32), // shuffled ring position -> submitter, once moved by the shuffle
+    AssignmentProgress(u64),
+    ContentHash(u64, BytesN<32>),
+    ReviewQueue(u64, Address), // authors a reviewer has been assigned
+    Review(u64, Address, Address), // (assignment, author, reviewer)
+    InstructorScore(u64, Address), // rubric total the instructor gave a submission nobody reviewed
+    OutlierCount(u64, Address),
+    OutlierReviewers(u64),
+    ReviewSeed(u64), // seed reviewers were assigned from
+    Admin,
+    CourseMetadataContract,
+}
+
+#[contract]
+pub struct PeerReviewContract;
+
+#[contractimpl]
+impl PeerReviewContract {
+    /// Initialize the contract with an admin and the CourseMetadataContract peer scores feed into
+    pub fn initialize(env: Env, admin: Address, course_metadata_contract: Address) {
+        if env.storage().instance().has(&PeerReviewKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&PeerReviewKey::Admin, &admin);
+        env.storage().instance().set(&PeerReviewKey::CourseMetadataContract, &course_metadata_contract);
+        env.storage().instance().set(&PeerReviewKey::AssignmentCount, &0u64);
+    }
+
+    /// Create a peer-reviewed assignment (course instructor only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_assignment(
+        env: Env,
+        instructor: Address,
+        course_id: String,
+        component_id: u32,
+        rubric: Vec<RubricCriterion>,
+        reviews_per_submission: u32,
+        outlier_threshold: u32,
+        submission_deadline: u64,
+        review_deadline: u64,
+    ) -> u64 {
+        instructor.require_auth();
+
+        let courses = Self::course_metadata_client(env.clone());
+        let course = match courses.try_get_course(&course_id) {
+            Ok(Ok(course)) => course,
+            _ => panic!("Course not found"),
+        };
+        if course.instructor != instructor {
+            panic!("Only course instructor can create assignments");
+        }
+
+        let gradebook = courses.get_gradebook(&course_id).unwrap_or_else(|| panic!("Gradebook not found"));
+        if component_id >= gradebook.components.len() {
+            panic!("Grade component not found");
+        }
+
+        if rubric.is_empty() || rubric.len() > MAX_RUBRIC_CRITERIA {
+            panic!("Rubric criteria count out of range");
+        }
+        if rubric.iter().any(|criterion| criterion.max_points == 0) {
+            panic!("Rubric criteria need points");
+        }
+        if reviews_per_submission == 0 || reviews_per_submission > MAX_REVIEWS_PER_SUBMISSION {
+            panic!("Reviews per submission out of range");
+        }
+        if outlier_threshold > 100 {
+            panic!("Outlier threshold must be 0-100");
+        }
+        if submission_deadline <= env.ledger().timestamp() || review_deadline <= submission_deadline {
+            panic!("Invalid assignment deadlines");
+        }
+
+        let assignment_id: u64 = env.storage().instance()
+            .get(&PeerReviewKey::AssignmentCount)
+            .unwrap_or(0) + 1;
+        let assignment = PeerAssignment {
+            id: assignment_id,
+            course_id,
+            instructor,
+            component_id,
+            rubric,
+            reviews_per_submission,
+            outlier_threshold,
+            submission_deadline,
+            review_deadline,
+        };
+
+        env.storage().persistent().set(&PeerReviewKey::Assignment(assignment_id), &assignment);
+        env.storage().instance().set(&PeerReviewKey::AssignmentCount, &assignment_id);
+
+        assignment_id
+    }
+
+    /// Register a submission by the hash of its content
+    pub fn submit(env: Env, author: Address, assignment_id: u64, content_hash: BytesN<32>) {
+        author.require_auth();
+
+        let assignment = Self::get_assignment(env.clone(), assignment_id);
+        if env.ledger().timestamp() > assignment.submission_deadline {
+            panic!("Submission deadline has passed");
+        }
+
+        let submission_key = PeerReviewKey::Submission(assignment_id, author.clone());
+        if env.storage().persistent().has(&submission_key) {
+            panic!("Already submitted");
+        }
+        let content_key = PeerReviewKey::ContentHash(assignment_id, content_hash.clone());
+        if env.storage().persistent().has(&content_key) {
+            panic!("Content already submitted");
+        }
+
+        let submitter_count = Self::get_submitter_count(env.clone(), assignment_id);
+
+        let submission = PeerSubmission {
+            author: author.clone(),
+            content_hash,
+            submitted_at: env.ledger().timestamp(),
+            reviewers: Vec::new(&env),
+            final_score: None,
+        };
+
+        env.storage().persistent().set(&submission_key, &submission);
+        env.storage().persistent().set(&content_key, &author);
+        env.storage().persistent().set(&PeerReviewKey::Submitter(assignment_id, submitter_count), &author);
+        env.storage().persistent().set(&PeerReviewKey::SubmitterCount(assignment_id), &(submitter_count + 1));
+    }
+
+    /// Assign reviewers once submissions close, up to `batch_size` steps per call; returns the steps remaining
+    pub fn assign_reviewers(env: Env, assignment_id: u64, batch_size: u32) -> u32 {
+        let assignment = Self::get_assignment(env.clone(), assignment_id);
+        if env.ledger().timestamp() <= assignment.submission_deadline {
+            panic!("Submissions are still open");
+        }
+
+        let count = Self::get_submitter_count(env.clone(), assignment_id);
+        if count <= assignment.reviews_per_submission {
+            panic!("Not enough submissions to review");
+        }
+
+        // The first call draws the seed from the host PRNG so submitters cannot grind their placement
+        let seed = match Self::get_review_seed(env.clone(), assignment_id) {
+            Some(seed) => seed,
+            None => {
+                let mut seed_material = Bytes::new(&env);
+                seed_material.append(&assignment_id.to_xdr(&env));
+                seed_material.append(&Bytes::from_array(&env, &env.prng().gen::<BytesN<32>>().to_array()));
+                let seed: BytesN<32> = env.crypto().sha256(&seed_material).into();
+                env.storage().persistent().set(&PeerReviewKey::ReviewSeed(assignment_id), &seed);
+                seed
+            }
+        };
+
+        let mut progress = Self::get_assignment_progress(env.clone(), assignment_id);
+        if progress.assigned == count {
+            panic!("Reviewers already assigned");
+        }
+
+        // Reseed per batch so the shuffle is reproducible from the seed however it was batched
+        let mut batch_material = Bytes::from_array(&env, &seed.to_array());
+        batch_material.append(&progress.shuffled.to_xdr(&env));
+        env.prng().seed(Bytes::from_array(&env, &env.crypto().sha256(&batch_material).to_array()));
+
+        let mut steps = batch_size.min(MAX_ASSIGNMENT_BATCH);
+
+        // Fisher-Yates shuffle of the submitters into ring order
+        while steps > 0 && progress.shuffled < count - 1 {
+            let position = progress.shuffled;
+            let swap = env.prng().gen_range::<u64>(position as u64..count as u64) as u32;
+            let current = Self::get_ring_member(env.clone(), assignment_id, position);
+            let other = Self::get_ring_member(env.clone(), assignment_id, swap);
+            env.storage().persistent().set(&PeerReviewKey::ReviewOrder(assignment_id, position), &other);
+            env.storage().persistent().set(&PeerReviewKey::ReviewOrder(assignment_id, swap), &current);
+
+            progress.shuffled += 1;
+            steps -= 1;
+        }
+
+        // Each learner reviews the next reviews_per_submission learners in the shuffled ring
+        while steps > 0 && progress.shuffled == count - 1 && progress.assigned < count {
+            let author = Self::get_ring_member(env.clone(), assignment_id, progress.assigned);
+            let mut submission = Self::get_submission(env.clone(), assignment_id, author.clone());
+
+            for offset in 1..=assignment.reviews_per_submission {
+                let reviewer = Self::get_ring_member(env.clone(), assignment_id, (progress.assigned + offset) % count);
+                submission.reviewers.push_back(reviewer.clone());
+
+                let queue_key = PeerReviewKey::ReviewQueue(assignment_id, reviewer);
+                let mut queue: Vec<Address> = env.storage().persistent()
+                    .get(&queue_key)
+                    .unwrap_or_else(|| Vec::new(&env));
+                queue.push_back(author.clone());
+                env.storage().persistent().set(&queue_key, &queue);
+            }
+
+            env.storage().persistent().set(&PeerReviewKey::Submission(assignment_id, author), &submission);
+            progress.assigned += 1;
+            steps -= 1;
+        }
+
+        env.storage().persistent().set(&PeerReviewKey::AssignmentProgress(assignment_id), &progress);
+
+        (count - 1 - progress.shuffled) + (count - progress.assigned)
+    }
+
+    /// Score an assigned submission against the rubric
+    pub fn submit_review(env: Env, reviewer: Address, assignment_id: u64, author: Address, scores: Vec<u32>) {
+        reviewer.require_auth();
+
+        let assignment = Self::get_assignment(env.clone(), assignment_id);
+        if env.ledger().timestamp() > assignment.review_deadline {
+            panic!("Review deadline has passed");
+        }
+
+        let submission = Self::get_submission(env.clone(), assignment_id, author.clone());
+        if !submission.reviewers.contains(&reviewer) {
+            panic!("Reviewer not assigned to submission");
+        }
+
+        let review_key = PeerReviewKey::Review(assignment_id, author, reviewer.clone());
+        if env.storage().persistent().has(&review_key) {
+            panic!("Review already submitted");
+        }
+
+        let total = Self::rubric_total(&assignment, &scores);
+
+        let review = PeerReview {
+            reviewer,
+            scores,
+            total,
+            submitted_at: env.ledger().timestamp(),
+            is_outlier: false,
+        };
+        env.storage().persistent().set(&review_key, &review);
+    }
+
+    /// Grade a submission against the rubric, used when it receives no peer reviews (assignment instructor only)
+    pub fn grade_submission(env: Env, instructor: Address, assignment_id: u64, author: Address, scores: Vec<u32>) {
+        instructor.require_auth();
+
+        let assignment = Self::get_assignment(env.clone(), assignment_id);
+        if assignment.instructor != instructor {
+            panic!("Only assignment instructor can grade submissions");
+        }
+
+        let submission = Self::get_submission(env.clone(), assignment_id, author.clone());
+        if submission.final_score.is_some() {
+            panic!("Submission already finalized");
+        }
+
+        let total = Self::rubric_total(&assignment, &scores);
+        env.storage().persistent().set(&PeerReviewKey::InstructorScore(assignment_id, author), &total);
+    }
+
+    /// Settle a submission after the review deadline: flag outlier reviews and record the median as the peer score
+    pub fn finalize_submission(env: Env, assignment_id: u64, author: Address) -> u32 {
+        let assignment = Self::get_assignment(env.clone(), assignment_id);
+        if env.ledger().timestamp() <= assignment.review_deadline {
+            panic!("Reviews are still open");
+        }
+
+        if Self::get_assignment_progress(env.clone(), assignment_id).assigned < Self::get_submitter_count(env.clone(), assignment_id) {
+            panic!("Reviewers not fully assigned");
+        }
+
+        let mut submission = Self::get_submission(env.clone(), assignment_id, author.clone());
+        if submission.final_score.is_some() {
+            panic!("Submission already finalized");
+        }
+
+        let mut reviews = Vec::new(&env);
+        for reviewer in submission.reviewers.iter() {
+            if let Some(review) = Self::get_review(env.clone(), assignment_id, author.clone(), reviewer) {
+                reviews.push_back(review);
+            }
+        }
+
+        // Without any reviews the instructor's grade stands in for the median
+        let rubric_max: u32 = assignment.rubric.iter().map(|criterion| criterion.max_points).sum();
+        let mut totals = Vec::new(&env);
+        for review in reviews.iter() {
+            totals.push_back(review.total);
+        }
+        let median = if totals.is_empty() {
+            env.storage().persistent()
+                .get(&PeerReviewKey::InstructorScore(assignment_id, author.clone()))
+                .unwrap_or_else(|| panic!("Submission has no reviews or instructor grade"))
+        } else {
+            Self::median(env.clone(), totals)
+        };
+        let max_distance = rubric_max * assignment.outlier_threshold / 100;
+
+        for mut review in reviews.iter() {
+            if review.total.abs_diff(median) <= max_distance {
+                continue;
+            }
+
+            review.is_outlier = true;
+            env.storage().persistent().set(
+                &PeerReviewKey::Review(assignment_id, author.clone(), review.reviewer.clone()),
+                &review,
+            );
+
+            let count_key = PeerReviewKey::OutlierCount(assignment_id, review.reviewer.clone());
+            let outlier_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            if outlier_count == 0 {
+                let mut outliers = Self::get_outlier_reviewers(env.clone(), assignment_id);
+                outliers.push_back(review.reviewer.clone());
+                env.storage().persistent().set(&PeerReviewKey::OutlierReviewers(assignment_id), &outliers);
+            }
+            env.storage().persistent().set(&count_key, &(outlier_count + 1));
+        }
+
+        let final_score = median * 100 / rubric_max;
+        submission.final_score = Some(final_score);
+        env.storage().persistent().set(&PeerReviewKey::Submission(assignment_id, author.clone()), &submission);
+
+        // Feed the peer score into the course gradebook; this contract must be a course grader
+        let courses = Self::course_metadata_client(env.clone());
+        let gradebook = courses.get_gradebook(&assignment.course_id).unwrap_or_else(|| panic!("Gradebook not found"));
+        let component = gradebook.components.get(assignment.component_id).unwrap();
+        courses.record_score(
+            &assignment.course_id,
+            &env.current_contract_address(),
+            &author,
+            &assignment.component_id,
+            &(median * component.max_score / rubric_max),
+        );
+
+        final_score
+    }
+
+    /// Get an assignment
+    pub fn get_assignment(env: Env, assignment_id: u64) -> PeerAssignment {
+        env.storage().persistent()
+            .get(&PeerReviewKey::Assignment(assignment_id))
+            .unwrap_or_else(|| panic!("Assignment not found"))
+    }
+
+    /// Get a learner's submission to an assignment
+    pub fn get_submission(env: Env, assignment_id: u64, author: Address) -> PeerSubmission {
+        env.storage().persistent()
+            .get(&PeerReviewKey::Submission(assignment_id, author))
+            .unwrap_or_else(|| panic!("Submission not found"))
+    }
+
+    /// Get the number of learners who submitted to an assignment
+    pub fn get_submitter_count(env: Env, assignment_id: u64) -> u32 {
+        env.storage().persistent()
+            .get(&PeerReviewKey::SubmitterCount(assignment_id))
+            .unwrap_or(0)
+    }
+
+    /// Get up to `limit` of the learners who submitted to an assignment starting at `cursor`, in submission order
+    pub fn get_submitters(env: Env, assignment_id: u64, cursor: u32, limit: u32) -> Vec<Address> {
+        let count = Self::get_submitter_count(env.clone(), assignment_id);
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut submitters = Vec::new(&env);
+        for index in cursor..end {
+            submitters.push_back(
+                env.storage().persistent()
+                    .get(&PeerReviewKey::Submitter(assignment_id, index))
+                    .unwrap(),
+            );
+        }
+
+        submitters
+    }
+
+    /// Get how far reviewer assignment has progressed
+    pub fn get_assignment_progress(env: Env, assignment_id: u64) -> ReviewAssignmentProgress {
+        env.storage().persistent()
+            .get(&PeerReviewKey::AssignmentProgress(assignment_id))
+            .unwrap_or(ReviewAssignmentProgress { shuffled: 0, assigned: 0 })
+    }
+
+    /// Get the seed reviewers are assigned from, once assignment has started
+    pub fn get_review_seed(env: Env, assignment_id: u64) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&PeerReviewKey::ReviewSeed(assignment_id))
+    }
+
+    /// Get the authors a reviewer has been assigned to review
+    pub fn get_review_queue(env: Env, assignment_id: u64, reviewer: Address) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&PeerReviewKey::ReviewQueue(assignment_id, reviewer))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get a reviewer's review of a submission
+    pub fn get_review(env: Env, assignment_id: u64, author: Address, reviewer: Address) -> Option<PeerReview> {
+        env.storage().persistent().get(&PeerReviewKey::Review(assignment_id, author, reviewer))
+    }
+
+    /// Get the reviewers with at least one review flagged as an outlier
+    pub fn get_outlier_reviewers(env: Env, assignment_id: u64) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&PeerReviewKey::OutlierReviewers(assignment_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the number of a reviewer's reviews flagged as outliers
+    pub fn get_outlier_count(env: Env, assignment_id: u64, reviewer: Address) -> u32 {
+        env.storage().persistent()
+            .get(&PeerReviewKey::OutlierCount(assignment_id, reviewer))
+            .unwrap_or(0)
+    }
+
+    /// Check scores against the rubric and return their total
+    fn rubric_total(assignment: &PeerAssignment, scores: &Vec<u32>) -> u32 {
+        if scores.len() != assignment.rubric.len() {
+            panic!("Score count does not match rubric");
+        }
+        let mut total = 0u32;
+        for (criterion, score) in assignment.rubric.iter().zip(scores.iter()) {
+            if score > criterion.max_points {
+                panic!("Score exceeds rubric criterion");
+            }
+            total += score;
+        }
+        total
+    }
+
+    /// The submitter at a ring position; positions the shuffle has not touched hold the submitter of the same index
+    fn get_ring_member(env: Env, assignment_id: u64, position: u32) -> Address {
+        env.storage().persistent()
+            .get(&PeerReviewKey::ReviewOrder(assignment_id, position))
+            .unwrap_or_else(|| env.storage().persistent()
+                .get(&PeerReviewKey::Submitter(assignment_id, position))
+                .unwrap())
+    }
+
+    /// Median of a list of totals; even-length lists average the middle pair, rounding down
+    fn median(env: Env, values: Vec<u32>) -> u32 {
+        let mut sorted: Vec<u32> = Vec::new(&env);
+        for value in values.iter() {
+            let position = sorted.iter().position(|existing| existing > value).unwrap_or(sorted.len() as usize);
+            sorted.insert(position as u32, value);
+        }
+
+        let middle = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            (sorted.get(middle - 1).unwrap() + sorted.get(middle).unwrap()) / 2
+        } else {
+            sorted.get(middle).unwrap()
+        }
+    }
+
+    /// Client for the configured CourseMetadataContract
+    fn course_metadata_client(env: Env) -> CourseMetadataContractClient<'static> {
+        let course_contract: Address = env.storage().instance()
+            .get(&PeerReviewKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        CourseMetadataContractClient::new(&env, &course_contract)
+    }
+}
@@ -0,0 +1,184 @@
+#![cfg(test)]
+
+use crate::peerReview::{PeerReviewContract, PeerReviewContractClient, RubricCriterion, MAX_ASSIGNMENT_BATCH};
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, NewCourse, GradeComponent, GradeComponentKind, GradeRounding};
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Address, BytesN, Env, String, Vec};
+
+/// Register a course graded entirely by one peer-reviewed assignment with four submissions
+fn setup<'a>(env: &Env) -> (PeerReviewContractClient<'a>, CourseMetadataContractClient<'a>, String, u64, Vec<Address>) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let instructor = Address::generate(env);
+
+    let courses = CourseMetadataContractClient::new(env, &env.register_contract(None, CourseMetadataContract));
+    courses.initialize(&admin);
+    let course_id = courses.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(env, "Technical Writing"),
+            description: String::from_str(env, "Writing for developers"),
+            category: String::from_str(env, "Writing"),
+            level: String::from_str(env, "beginner"),
+            duration: 10,
+            price: 0,
+            prerequisites: vec![env],
+            learning_objectives: vec![env],
+            syllabus: String::from_str(env, "QmSyllabus"),
+            thumbnail_url: String::from_str(env, "https://example.com/thumbnail.jpg"),
+            tags: vec![env],
+            language: String::from_str(env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+    courses.set_gradebook(
+        &course_id,
+        &instructor,
+        &vec![env, GradeComponent {
+            id: 0,
+            name: String::from_str(env, "Essay"),
+            kind: GradeComponentKind::Assignment,
+            weight: 100,
+            max_score: 100,
+        }],
+        &GradeRounding::Down,
+        &50,
+    );
+
+    let client = PeerReviewContractClient::new(env, &env.register_contract(None, PeerReviewContract));
+    client.initialize(&admin, &courses.address);
+    courses.add_grader(&course_id, &instructor, &client.address);
+
+    let assignment_id = client.create_assignment(
+        &instructor,
+        &course_id,
+        &0,
+        &vec![
+            env,
+            RubricCriterion { name: String::from_str(env, "Clarity"), max_points: 10 },
+            RubricCriterion { name: String::from_str(env, "Accuracy"), max_points: 10 },
+        ],
+        &3,
+        &20,
+        &100,
+        &200,
+    );
+
+    let mut learners = Vec::new(env);
+    for index in 0..4u8 {
+        let learner = Address::generate(env);
+        client.submit(&learner, &assignment_id, &BytesN::from_array(env, &[index; 32]));
+        learners.push_back(learner);
+    }
+
+    (client, courses, course_id, assignment_id, learners)
+}
+
+#[test]
+fn test_peer_review_flow() {
+    let env = Env::default();
+    let (client, courses, course_id, assignment_id, learners) = setup(&env);
+
+    env.ledger().set_timestamp(150);
+    assert_eq!(client.assign_reviewers(&assignment_id, &MAX_ASSIGNMENT_BATCH), 0);
+    assert!(client.get_review_seed(&assignment_id).is_some());
+
+    // Every learner reviews three others and nobody reviews themselves
+    let author = learners.get(0).unwrap();
+    let reviewers = client.get_submission(&assignment_id, &author).reviewers;
+    assert_eq!(reviewers.len(), 3);
+    assert!(!reviewers.contains(&author));
+    for learner in learners.iter() {
+        assert_eq!(client.get_review_queue(&assignment_id, &learner).len(), 3);
+    }
+
+    // Two reviewers agree, the third is far off
+    client.submit_review(&reviewers.get(0).unwrap(), &assignment_id, &author, &vec![&env, 8, 8]);
+    client.submit_review(&reviewers.get(1).unwrap(), &assignment_id, &author, &vec![&env, 7, 8]);
+    client.submit_review(&reviewers.get(2).unwrap(), &assignment_id, &author, &vec![&env, 1, 2]);
+
+    env.ledger().set_timestamp(250);
+    // Median 15 of 20 points
+    assert_eq!(client.finalize_submission(&assignment_id, &author), 75);
+
+    assert_eq!(client.get_outlier_reviewers(&assignment_id), vec![&env, reviewers.get(2).unwrap()]);
+    assert!(client.get_review(&assignment_id, &author, &reviewers.get(2).unwrap()).unwrap().is_outlier);
+
+    // The peer score lands in the gradebook
+    assert_eq!(courses.get_score(&course_id, &author, &0).unwrap().score, 75);
+    assert_eq!(courses.compute_grade(&course_id, &author), 75);
+}
+
+#[test]
+fn test_unreviewed_submission_uses_instructor_grade() {
+    let env = Env::default();
+    let (client, courses, course_id, assignment_id, learners) = setup(&env);
+    let instructor = client.get_assignment(&assignment_id).instructor;
+
+    env.ledger().set_timestamp(150);
+    client.assign_reviewers(&assignment_id, &MAX_ASSIGNMENT_BATCH);
+
+    // Nobody reviewed the submission, so it cannot settle until the instructor grades it
+    let author = learners.get(0).unwrap();
+    env.ledger().set_timestamp(250);
+    assert!(client.try_finalize_submission(&assignment_id, &author).is_err());
+
+    client.grade_submission(&instructor, &assignment_id, &author, &vec![&env, 9, 7]);
+    assert_eq!(client.finalize_submission(&assignment_id, &author), 80);
+    assert_eq!(courses.get_score(&course_id, &author, &0).unwrap().score, 80);
+}
+
+#[test]
+#[should_panic(expected = "Reviewer not assigned to submission")]
+fn test_unassigned_review_rejected() {
+    let env = Env::default();
+    let (client, _courses, _course_id, assignment_id, learners) = setup(&env);
+
+    env.ledger().set_timestamp(150);
+    client.assign_reviewers(&assignment_id, &MAX_ASSIGNMENT_BATCH);
+
+    let author = learners.get(0).unwrap();
+    client.submit_review(&author, &assignment_id, &author, &vec![&env, 10, 10]);
+}
+
+#[test]
+#[should_panic(expected = "Content already submitted")]
+fn test_duplicate_content_rejected() {
+    let env = Env::default();
+    let (client, _courses, _course_id, assignment_id, _learners) = setup(&env);
+
+    client.submit(&Address::generate(&env), &assignment_id, &BytesN::from_array(&env, &[0; 32]));
+}
+
+#[test]
+fn test_reviewers_assigned_in_batches() {
+    let env = Env::default();
+    let (client, _courses, _course_id, assignment_id, learners) = setup(&env);
+    assert_eq!(client.get_submitter_count(&assignment_id), 4);
+    assert_eq!(client.get_submitters(&assignment_id, &1, &2), vec![&env, learners.get(1).unwrap(), learners.get(2).unwrap()]);
+
+    // Three shuffle steps then four assignment steps
+    env.ledger().set_timestamp(150);
+    assert_eq!(client.assign_reviewers(&assignment_id, &2), 5);
+    let seed = client.get_review_seed(&assignment_id);
+    assert!(seed.is_some());
+    assert_eq!(client.assign_reviewers(&assignment_id, &2), 3);
+    assert_eq!(client.assign_reviewers(&assignment_id, &2), 1);
+
+    // Scores cannot settle until every submission has its reviewers
+    env.ledger().set_timestamp(250);
+    assert!(client.try_finalize_submission(&assignment_id, &learners.get(0).unwrap()).is_err());
+
+    assert_eq!(client.assign_reviewers(&assignment_id, &2), 0);
+    assert_eq!(client.get_review_seed(&assignment_id), seed);
+    assert!(client.try_assign_reviewers(&assignment_id, &2).is_err());
+
+    // Submission order is kept for audit and every learner is in the ring exactly once
+    assert_eq!(client.get_submitters(&assignment_id, &0, &10), learners);
+    for learner in learners.iter() {
+        let reviewers = client.get_submission(&assignment_id, &learner).reviewers;
+        assert_eq!(reviewers.len(), 3);
+        assert!(!reviewers.contains(&learner));
+        assert_eq!(client.get_review_queue(&assignment_id, &learner).len(), 3);
+    }
+}
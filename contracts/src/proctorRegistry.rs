@@ -0,0 +1,230 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SessionStatus {
+    Clean, // the proctor saw no integrity issues
+    Flagged, // the proctor reported issues; awaiting admin review
+    Cleared, // flagged, then approved by an admin
+    Rejected, // flagged, then rejected by an admin
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProctorClearance {
+    NotRequired, // the course has no proctored exam
+    Cleared,
+    Missing,
+    UnderReview,
+    Rejected,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SessionAttestation {
+    pub exam_id: String,
+    pub learner: Address,
+    pub proctor: Address,
+    pub session_hash: BytesN<32>, // hash of the session recording and logs, stored off-chain
+    pub status: SessionStatus,
+    pub attested_at: u64,
+    pub reviewed_by: Option<Address>,
+    pub reviewed_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionRef {
+    pub exam_id: String,
+    pub learner: Address,
+}
+
+#[contracttype]
+pub enum ProctorKey {
+    Admin,
+    Proctor(Address),
+    CourseExam(String),
+    Session(String, Address),
+    FlaggedSessions,
+}
+
+#[contract]
+pub struct ProctorRegistryContract;
+
+#[contractimpl]
+impl ProctorRegistryContract {
+    /// Initialize the registry with an admin address
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&ProctorKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&ProctorKey::Admin, &admin);
+    }
+
+    /// Approve a proctor (Admin only)
+    pub fn add_proctor(env: Env, admin: Address, proctor: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().set(&ProctorKey::Proctor(proctor), &true);
+    }
+
+    /// Withdraw a proctor's approval (Admin only)
+    pub fn remove_proctor(env: Env, admin: Address, proctor: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().remove(&ProctorKey::Proctor(proctor));
+    }
+
+    /// Check whether an address is an approved proctor
+    pub fn is_proctor(env: Env, proctor: Address) -> bool {
+        env.storage().persistent()
+            .get(&ProctorKey::Proctor(proctor))
+            .unwrap_or(false)
+    }
+
+    /// Require a proctored exam before a course's credentials are issued (Admin only)
+    pub fn set_course_exam(env: Env, admin: Address, course_id: String, exam_id: String) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().set(&ProctorKey::CourseExam(course_id), &exam_id);
+    }
+
+    /// Stop requiring a proctored exam for a course (Admin only)
+    pub fn remove_course_exam(env: Env, admin: Address, course_id: String) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().persistent().remove(&ProctorKey::CourseExam(course_id));
+    }
+
+    /// Get the proctored exam a course requires, if any
+    pub fn get_course_exam(env: Env, course_id: String) -> Option<String> {
+        env.storage().persistent().get(&ProctorKey::CourseExam(course_id))
+    }
+
+    /// Attest a learner's exam session; sessions with integrity issues are flagged for admin review
+    ///
+    /// Reviewed sessions can only be attested again after an admin resets them
+    pub fn attest_session(
+        env: Env,
+        proctor: Address,
+        exam_id: String,
+        learner: Address,
+        session_hash: BytesN<32>,
+        integrity_met: bool,
+    ) {
+        proctor.require_auth();
+
+        if !Self::is_proctor(env.clone(), proctor.clone()) {
+            panic!("Only approved proctors can attest sessions");
+        }
+
+        let session_key = ProctorKey::Session(exam_id.clone(), learner.clone());
+        if let Some(existing) = env.storage().persistent().get::<_, SessionAttestation>(&session_key) {
+            match existing.status {
+                SessionStatus::Clean => {}
+                SessionStatus::Flagged => panic!("Session is under review"),
+                SessionStatus::Cleared | SessionStatus::Rejected => panic!("Session already reviewed"),
+            }
+        }
+
+        let status = if integrity_met {
+            SessionStatus::Clean
+        } else {
+            let mut flagged = Self::get_flagged_sessions(env.clone());
+            flagged.push_back(SessionRef {
+                exam_id: exam_id.clone(),
+                learner: learner.clone(),
+            });
+            env.storage().persistent().set(&ProctorKey::FlaggedSessions, &flagged);
+            SessionStatus::Flagged
+        };
+
+        let attestation = SessionAttestation {
+            exam_id,
+            learner,
+            proctor,
+            session_hash,
+            status,
+            attested_at: env.ledger().timestamp(),
+            reviewed_by: None,
+            reviewed_at: None,
+        };
+        env.storage().persistent().set(&session_key, &attestation);
+    }
+
+    /// Approve or reject a flagged session (Admin only)
+    pub fn review_session(env: Env, admin: Address, exam_id: String, learner: Address, approve: bool) {
+        Self::require_admin(env.clone(), admin.clone());
+
+        let mut attestation = Self::get_session(env.clone(), exam_id.clone(), learner.clone())
+            .unwrap_or_else(|| panic!("Session not found"));
+        if attestation.status != SessionStatus::Flagged {
+            panic!("Session is not flagged");
+        }
+
+        attestation.status = if approve { SessionStatus::Cleared } else { SessionStatus::Rejected };
+        attestation.reviewed_by = Some(admin);
+        attestation.reviewed_at = Some(env.ledger().timestamp());
+
+        let mut flagged = Self::get_flagged_sessions(env.clone());
+        if let Some(index) = flagged.first_index_of(SessionRef { exam_id: exam_id.clone(), learner: learner.clone() }) {
+            flagged.remove(index);
+        }
+        env.storage().persistent().set(&ProctorKey::FlaggedSessions, &flagged);
+        env.storage().persistent().set(&ProctorKey::Session(exam_id, learner), &attestation);
+    }
+
+    /// Discard a learner's session attestation so the exam can be retaken (Admin only)
+    pub fn reset_session(env: Env, admin: Address, exam_id: String, learner: Address) {
+        Self::require_admin(env.clone(), admin);
+
+        let session_key = ProctorKey::Session(exam_id.clone(), learner.clone());
+        if !env.storage().persistent().has(&session_key) {
+            panic!("Session not found");
+        }
+
+        let mut flagged = Self::get_flagged_sessions(env.clone());
+        if let Some(index) = flagged.first_index_of(SessionRef { exam_id, learner }) {
+            flagged.remove(index);
+            env.storage().persistent().set(&ProctorKey::FlaggedSessions, &flagged);
+        }
+        env.storage().persistent().remove(&session_key);
+    }
+
+    /// Get a learner's session attestation for an exam
+    pub fn get_session(env: Env, exam_id: String, learner: Address) -> Option<SessionAttestation> {
+        env.storage().persistent().get(&ProctorKey::Session(exam_id, learner))
+    }
+
+    /// Get the flagged sessions awaiting admin review, oldest first
+    pub fn get_flagged_sessions(env: Env) -> Vec<SessionRef> {
+        env.storage().persistent()
+            .get(&ProctorKey::FlaggedSessions)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Check whether a learner may be issued a credential for a course
+    pub fn get_clearance(env: Env, course_id: String, learner: Address) -> ProctorClearance {
+        let exam_id = match Self::get_course_exam(env.clone(), course_id) {
+            Some(exam_id) => exam_id,
+            None => return ProctorClearance::NotRequired,
+        };
+
+        match Self::get_session(env, exam_id, learner).map(|attestation| attestation.status) {
+            Some(SessionStatus::Clean) | Some(SessionStatus::Cleared) => ProctorClearance::Cleared,
+            Some(SessionStatus::Flagged) => ProctorClearance::UnderReview,
+            Some(SessionStatus::Rejected) => ProctorClearance::Rejected,
+            None => ProctorClearance::Missing,
+        }
+    }
+
+    /// Require authorization from the admin address
+    fn require_admin(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&ProctorKey::Admin)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+
+        if caller != admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+}
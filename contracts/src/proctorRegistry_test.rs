@@ -0,0 +1,176 @@
+#![cfg(test)]
+
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, NewCourse};
+use crate::proctorRegistry::{ProctorClearance, ProctorRegistryContract, ProctorRegistryContractClient, SessionStatus};
+use crate::{StarkEdContract, StarkEdContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+fn setup<'a>(env: &Env) -> (ProctorRegistryContractClient<'a>, StarkEdContractClient<'a>, CourseMetadataContractClient<'a>, Address, Address) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let proctor = Address::generate(env);
+
+    let registry = ProctorRegistryContractClient::new(env, &env.register_contract(None, ProctorRegistryContract));
+    registry.initialize(&admin);
+    registry.add_proctor(&admin, &proctor);
+    registry.set_course_exam(&admin, &String::from_str(env, "course_1"), &String::from_str(env, "final_exam"));
+
+    let starked = StarkEdContractClient::new(env, &env.register_contract(None, StarkEdContract));
+    starked.initialize(&admin);
+    starked.set_proctor_registry_contract(&admin, &registry.address);
+
+    let courses = CourseMetadataContractClient::new(env, &env.register_contract(None, CourseMetadataContract));
+    courses.initialize(&admin);
+    starked.set_course_metadata_contract(&admin, &courses.address);
+    let course_id = courses.create_course(
+        &Address::generate(env),
+        &NewCourse {
+            title: String::from_str(env, "Rust Fundamentals"),
+            description: String::from_str(env, "Completed the Rust fundamentals course"),
+            category: String::from_str(env, "Programming"),
+            level: String::from_str(env, "beginner"),
+            duration: 10,
+            price: 0,
+            prerequisites: vec![env],
+            learning_objectives: vec![env],
+            syllabus: String::from_str(env, "QmSyllabus"),
+            thumbnail_url: String::from_str(env, "https://example.com/thumbnail.jpg"),
+            tags: vec![env],
+            language: String::from_str(env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+    starked.create_template(
+        &admin,
+        &course_id,
+        &String::from_str(env, "{course}"),
+        &String::from_str(env, "Completed the Rust fundamentals course"),
+        &String::from_str(env, "QmDesign"),
+        &None,
+        &0,
+    );
+
+    (registry, starked, courses, admin, proctor)
+}
+
+/// Issue the course credential for a fresh verified completion of `course_1`
+fn issue(env: &Env, starked: &StarkEdContractClient, courses: &CourseMetadataContractClient, admin: &Address, learner: &Address) -> u64 {
+    let completion_id = courses.record_completion(
+        &String::from_str(env, "course_1"),
+        learner,
+        &90,
+        &String::from_str(env, "QmCertificateHash"),
+        &vec![env],
+    );
+    courses.verify_completion(&completion_id, admin);
+
+    starked.issue_from_template(admin, &1, learner, &completion_id)
+}
+
+#[test]
+fn test_flagged_session_cleared_by_review() {
+    let env = Env::default();
+    let (registry, starked, courses, admin, proctor) = setup(&env);
+    let learner = Address::generate(&env);
+    let exam_id = String::from_str(&env, "final_exam");
+
+    registry.attest_session(&proctor, &exam_id, &learner, &BytesN::from_array(&env, &[1; 32]), &false);
+    assert_eq!(registry.get_clearance(&String::from_str(&env, "course_1"), &learner), ProctorClearance::UnderReview);
+    assert_eq!(registry.get_flagged_sessions().len(), 1);
+
+    registry.review_session(&admin, &exam_id, &learner, &true);
+    let session = registry.get_session(&exam_id, &learner).unwrap();
+    assert_eq!(session.status, SessionStatus::Cleared);
+    assert_eq!(session.reviewed_by, Some(admin.clone()));
+    assert_eq!(registry.get_flagged_sessions().len(), 0);
+
+    let credential_id = issue(&env, &starked, &courses, &admin, &learner);
+    assert_eq!(starked.get_credential(&credential_id).recipient, learner);
+}
+
+#[test]
+#[should_panic(expected = "Proctored exam attestation required")]
+fn test_proctored_course_requires_attestation() {
+    let env = Env::default();
+    let (_registry, starked, courses, admin, _proctor) = setup(&env);
+
+    issue(&env, &starked, &courses, &admin, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Proctored session is under review")]
+fn test_flagged_session_blocks_issuance() {
+    let env = Env::default();
+    let (registry, starked, courses, admin, proctor) = setup(&env);
+    let learner = Address::generate(&env);
+
+    registry.attest_session(&proctor, &String::from_str(&env, "final_exam"), &learner, &BytesN::from_array(&env, &[1; 32]), &false);
+    issue(&env, &starked, &courses, &admin, &learner);
+}
+
+#[test]
+#[should_panic(expected = "Session already reviewed")]
+fn test_rejected_session_cannot_be_reattested() {
+    let env = Env::default();
+    let (registry, _starked, _courses, admin, proctor) = setup(&env);
+    let learner = Address::generate(&env);
+    let exam_id = String::from_str(&env, "final_exam");
+
+    registry.attest_session(&proctor, &exam_id, &learner, &BytesN::from_array(&env, &[1; 32]), &false);
+    registry.review_session(&admin, &exam_id, &learner, &false);
+    registry.attest_session(&proctor, &exam_id, &learner, &BytesN::from_array(&env, &[2; 32]), &true);
+}
+
+#[test]
+fn test_reset_session_allows_retake() {
+    let env = Env::default();
+    let (registry, starked, courses, admin, proctor) = setup(&env);
+    let learner = Address::generate(&env);
+    let exam_id = String::from_str(&env, "final_exam");
+    let course_id = String::from_str(&env, "course_1");
+
+    registry.attest_session(&proctor, &exam_id, &learner, &BytesN::from_array(&env, &[1; 32]), &false);
+    registry.review_session(&admin, &exam_id, &learner, &false);
+    assert_eq!(registry.get_clearance(&course_id, &learner), ProctorClearance::Rejected);
+
+    registry.reset_session(&admin, &exam_id, &learner);
+    assert_eq!(registry.get_clearance(&course_id, &learner), ProctorClearance::Missing);
+
+    registry.attest_session(&proctor, &exam_id, &learner, &BytesN::from_array(&env, &[2; 32]), &true);
+    let credential_id = issue(&env, &starked, &courses, &admin, &learner);
+    assert_eq!(starked.get_credential(&credential_id).recipient, learner);
+}
+
+#[test]
+#[should_panic(expected = "Only approved proctors can attest sessions")]
+fn test_unapproved_proctor_rejected() {
+    let env = Env::default();
+    let (registry, _starked, _courses, _admin, _proctor) = setup(&env);
+
+    registry.attest_session(
+        &Address::generate(&env),
+        &String::from_str(&env, "final_exam"),
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[1; 32]),
+        &true,
+    );
+}
+
+#[test]
+fn test_admin_issuance_skips_proctor_gate() {
+    let env = Env::default();
+    let (_registry, starked, _courses, admin, _proctor) = setup(&env);
+    let learner = Address::generate(&env);
+
+    // Only completion-based issuance waits for a proctored session
+    let credential_id = starked.issue_credential(
+        &admin,
+        &learner,
+        &String::from_str(&env, "Rust Fundamentals"),
+        &String::from_str(&env, "Completed the Rust fundamentals course"),
+        &String::from_str(&env, "course_1"),
+        &String::from_str(&env, "QmCertificateHash"),
+    );
+    assert_eq!(starked.get_credential(&credential_id).recipient, learner);
+}
@@ -1,7 +1,28 @@
-#![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+use crate::courseMetadata::{CourseMetadata, CourseMetadataContractClient, CourseStatus, DeadlineStatus};
+use crate::eventLogger::EventLoggerContractClient;
+use crate::analyticsStorage::AnalyticsContractClient;
+
+/// Upper bound on the number of lessons tracked per course
+pub const MAX_LESSONS: u32 = 1024;
+
+/// Number of progress snapshots stored per history chunk
+pub const HISTORY_CHUNK_SIZE: u32 = 50;
+
+/// Upper bound on the page size of history queries
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Longest study session that can be recorded at once
+pub const MAX_SESSION_MINUTES: u32 = 240;
+
+/// Most study time that can be recorded per day
+pub const MAX_DAILY_STUDY_MINUTES: u32 = 960;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
 
 #[contracttype]
+#[derive(Clone, Debug)]
 pub struct UserProgress {
     pub user: Address,
     pub course_id: String,
@@ -11,9 +32,138 @@ pub struct UserProgress {
     pub last_updated: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressSnapshot {
+    pub lessons_completed: u32,
+    pub total_lessons: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StudyStats {
+    pub total_minutes: u64,
+    pub session_count: u64,
+    pub current_streak: u32, // consecutive active days, ending today or yesterday
+    pub longest_streak: u32,
+    pub last_active_day: u64, // ledger timestamp / 86400
+    pub day_minutes: u32, // study minutes recorded on last_active_day
+    pub last_session_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CourseProgressStats {
+    pub learners: u32,
+    pub active_learners: u32, // learners with progress who have not completed
+    pub completions: u32,
+    pub total_progress_bps: u64, // sum of every learner's progress in basis points
+    pub avg_progress_bps: u32, // Basis points (0-10000)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttemptScoring {
+    Best, // the attempt with the highest grade counts
+    Latest, // the most recent attempt counts
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttemptPolicy {
+    pub max_attempts: u32, // 0 for unlimited
+    pub scoring: AttemptScoring,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttemptRecord {
+    pub attempt: u32, // starts at 1
+    pub lessons_completed: u32,
+    pub total_lessons: u32,
+    pub is_completed: bool,
+    pub grade: Option<u32>, // 0-100
+    pub started_at: u64,
+    pub ended_at: Option<u64>, // set once the learner starts another attempt
+}
+
+/// A lesson completed after its cohort due date
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateLesson {
+    pub lesson_index: u32,
+    pub status: DeadlineStatus,
+    pub recorded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneHook {
+    EventOnly, // publish a contract event
+    LogAchievement, // also log a user achievement through EventLoggerContract
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub percent: u32, // 1-100
+    pub hook: MilestoneHook,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttestationMode {
+    SelfReported, // learners report every lesson themselves
+    GradedLessons, // graded lessons need an instructor or oracle signature
+    AllLessons, // every lesson needs an instructor or oracle signature
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AttestationPolicy {
+    pub mode: AttestationMode,
+    pub graded_lessons: Vec<u32>,
+}
+
+/// The message an instructor or oracle signs to attest a lesson completion
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LessonAttestation {
+    pub contract: Address,
+    pub user: Address,
+    pub course_id: String,
+    pub attempt: u32,
+    pub lesson_index: u32,
+}
+
 #[contracttype]
 pub enum ProgressKey {
     UserProgress(Address, String),
+    LessonBitmap(Address, String),
+    Admin,
+    CourseMetadataContract,
+    AttestationPolicy(String),
+    InstructorKey(String),
+    GradingOracle(BytesN<32>),
+    HistoryChunk(Address, String, u32, u32),
+    HistoryLength(Address, String, u32),
+    CompletedAt(Address, String, u32),
+    MilestonesReached(Address, String, u32),
+    StudyStats(Address),
+    CourseStudyMinutes(Address, String),
+    StreakThresholds,
+    StreakAwards(Address),
+    CourseMilestones(String),
+    EventLoggerContract,
+    UserCourses(Address),
+    CourseLearners(String, u32),
+    CourseStats(String),
+    LateLessons(Address, String, u32),
+    AttemptPolicy(String),
+    AttemptCount(Address, String),
+    Attempt(Address, String, u32),
+    AnalyticsContract,
 }
 
 #[contract]
@@ -21,16 +171,701 @@ pub struct CourseProgressContract;
 
 #[contractimpl]
 impl CourseProgressContract {
+    /// Initialize the contract with an admin and the CourseMetadataContract to validate against
+    pub fn initialize(env: Env, admin: Address, course_metadata_contract: Address) {
+        if env.storage().instance().has(&ProgressKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&ProgressKey::Admin, &admin);
+        env.storage().instance().set(&ProgressKey::CourseMetadataContract, &course_metadata_contract);
+    }
+
+    /// Mark the first `lessons_completed` lessons of a course as completed
     pub fn record_progress(
         env: Env,
         user: Address,
         course_id: String,
         lessons_completed: u32,
-        total_lessons: u32,
     ) {
         user.require_auth();
 
-        let is_completed = lessons_completed >= total_lessons;
+        let (total_lessons, required_lessons) = Self::course_lessons(env.clone(), course_id.clone());
+        if lessons_completed > total_lessons {
+            panic!("Lessons completed exceeds total lessons");
+        }
+
+        if let Some(current) = Self::get_progress(env.clone(), user.clone(), course_id.clone()) {
+            if lessons_completed < current.lessons_completed.min(total_lessons) {
+                panic!("Progress cannot go backwards");
+            }
+        }
+
+        let policy = Self::get_attestation_policy(env.clone(), course_id.clone());
+        let mut bitmap = Self::get_bitmap(env.clone(), user.clone(), course_id.clone());
+        let mut new_lessons = Vec::new(&env);
+        for lesson_index in 0..lessons_completed {
+            if !Self::bit_is_set(&bitmap, lesson_index) {
+                if Self::requires_attestation(&policy, lesson_index) {
+                    panic!("Lesson requires attestation");
+                }
+                new_lessons.push_back(lesson_index);
+            }
+            Self::set_lesson_bit(&mut bitmap, lesson_index);
+        }
+
+        Self::flag_late_lessons(env.clone(), user.clone(), course_id.clone(), new_lessons);
+        Self::save_progress(env, user, course_id, bitmap, total_lessons, required_lessons);
+    }
+
+    /// Mark a single lesson as completed
+    pub fn complete_lesson(
+        env: Env,
+        user: Address,
+        course_id: String,
+        lesson_index: u32,
+    ) -> UserProgress {
+        user.require_auth();
+
+        let (total_lessons, required_lessons) = Self::course_lessons(env.clone(), course_id.clone());
+        if lesson_index >= total_lessons {
+            panic!("Lesson index out of range");
+        }
+
+        let policy = Self::get_attestation_policy(env.clone(), course_id.clone());
+        if Self::requires_attestation(&policy, lesson_index) {
+            panic!("Lesson requires attestation");
+        }
+
+        let mut bitmap = Self::get_bitmap(env.clone(), user.clone(), course_id.clone());
+        if !Self::bit_is_set(&bitmap, lesson_index) {
+            Self::flag_late_lessons(env.clone(), user.clone(), course_id.clone(), Vec::from_array(&env, [lesson_index]));
+        }
+        Self::set_lesson_bit(&mut bitmap, lesson_index);
+
+        Self::save_progress(env, user, course_id, bitmap, total_lessons, required_lessons)
+    }
+
+    /// Mark a lesson as completed with a signature from the instructor or a grading oracle
+    pub fn complete_attested_lesson(
+        env: Env,
+        user: Address,
+        course_id: String,
+        lesson_index: u32,
+        signer_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> UserProgress {
+        user.require_auth();
+
+        let (total_lessons, required_lessons) = Self::course_lessons(env.clone(), course_id.clone());
+        if lesson_index >= total_lessons {
+            panic!("Lesson index out of range");
+        }
+
+        let is_instructor_key = Self::get_instructor_key(env.clone(), course_id.clone()) == Some(signer_key.clone());
+        if !is_instructor_key && !Self::is_grading_oracle(env.clone(), signer_key.clone()) {
+            panic!("Unknown attestation key");
+        }
+
+        let attempt = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+        let payload = Self::attestation_payload(env.clone(), user.clone(), course_id.clone(), attempt, lesson_index);
+        env.crypto().ed25519_verify(&signer_key, &payload, &signature);
+
+        let mut bitmap = Self::get_bitmap(env.clone(), user.clone(), course_id.clone());
+        if !Self::bit_is_set(&bitmap, lesson_index) {
+            Self::flag_late_lessons(env.clone(), user.clone(), course_id.clone(), Vec::from_array(&env, [lesson_index]));
+        }
+        Self::set_lesson_bit(&mut bitmap, lesson_index);
+
+        Self::save_progress(env, user, course_id, bitmap, total_lessons, required_lessons)
+    }
+
+    /// Set how many attempts a course allows and which attempt counts (course instructor only)
+    pub fn set_attempt_policy(env: Env, course_id: String, max_attempts: u32, scoring: AttemptScoring) {
+        let course = Self::get_active_course(env.clone(), course_id.clone());
+        course.instructor.require_auth();
+
+        let policy = AttemptPolicy { max_attempts, scoring };
+        env.storage().persistent().set(&ProgressKey::AttemptPolicy(course_id), &policy);
+    }
+
+    /// Get the attempt policy of a course; by default attempts are unlimited and the latest counts
+    pub fn get_attempt_policy(env: Env, course_id: String) -> AttemptPolicy {
+        env.storage().persistent()
+            .get(&ProgressKey::AttemptPolicy(course_id))
+            .unwrap_or(AttemptPolicy {
+                max_attempts: 0,
+                scoring: AttemptScoring::Latest,
+            })
+    }
+
+    /// Close the current attempt and reset progress for a retake, keeping the old attempt for audit
+    pub fn start_new_attempt(env: Env, user: Address, course_id: String) -> u32 {
+        user.require_auth();
+
+        let (total_lessons, required_lessons) = Self::course_lessons(env.clone(), course_id.clone());
+        if Self::get_progress(env.clone(), user.clone(), course_id.clone()).is_none() {
+            panic!("No attempt to retake");
+        }
+
+        let attempt = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+        let policy = Self::get_attempt_policy(env.clone(), course_id.clone());
+        if policy.max_attempts != 0 && attempt >= policy.max_attempts {
+            panic!("Attempt limit reached");
+        }
+
+        let now = env.ledger().timestamp();
+        let mut record = Self::get_attempt(env.clone(), user.clone(), course_id.clone(), attempt);
+        record.ended_at = Some(now);
+        env.storage().persistent().set(&ProgressKey::Attempt(user.clone(), course_id.clone(), attempt), &record);
+
+        env.storage().persistent().set(&ProgressKey::AttemptCount(user.clone(), course_id.clone()), &(attempt + 1));
+        env.storage().persistent().set(&ProgressKey::Attempt(user.clone(), course_id.clone(), attempt + 1), &AttemptRecord {
+            attempt: attempt + 1,
+            lessons_completed: 0,
+            total_lessons,
+            is_completed: false,
+            grade: None,
+            started_at: now,
+            ended_at: None,
+        });
+
+        // Reset through save_progress so the new attempt's history and course stats see the retake
+        Self::save_progress(env.clone(), user, course_id, Vec::new(&env), total_lessons, required_lessons);
+
+        attempt + 1
+    }
+
+    /// Grade a learner's current attempt (course instructor only)
+    pub fn set_attempt_grade(env: Env, user: Address, course_id: String, grade: u32) {
+        let course = Self::get_active_course(env.clone(), course_id.clone());
+        course.instructor.require_auth();
+
+        if grade > 100 {
+            panic!("Grade must be 0-100");
+        }
+
+        let attempt = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+        let mut record = Self::get_attempt(env.clone(), user.clone(), course_id.clone(), attempt);
+        record.grade = Some(grade);
+        env.storage().persistent().set(&ProgressKey::Attempt(user, course_id, attempt), &record);
+    }
+
+    /// Grade a learner's current attempt with the course gradebook's computed grade (course instructor only)
+    pub fn grade_from_gradebook(env: Env, user: Address, course_id: String) -> u32 {
+        let course_contract: Address = env.storage().instance()
+            .get(&ProgressKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        let grade = CourseMetadataContractClient::new(&env, &course_contract).compute_grade(&course_id, &user);
+
+        Self::set_attempt_grade(env, user, course_id, grade);
+        grade
+    }
+
+    /// Get the number of a learner's current attempt at a course, starting at 1
+    pub fn get_attempt_number(env: Env, user: Address, course_id: String) -> u32 {
+        env.storage().persistent()
+            .get(&ProgressKey::AttemptCount(user, course_id))
+            .unwrap_or(1)
+    }
+
+    /// Get an attempt; the current attempt reflects live progress
+    pub fn get_attempt(env: Env, user: Address, course_id: String, attempt: u32) -> AttemptRecord {
+        let current = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+        if attempt == 0 || attempt > current {
+            panic!("Attempt not found");
+        }
+
+        let mut record: AttemptRecord = env.storage().persistent()
+            .get(&ProgressKey::Attempt(user.clone(), course_id.clone(), attempt))
+            .unwrap_or(AttemptRecord {
+                attempt,
+                lessons_completed: 0,
+                total_lessons: 0,
+                is_completed: false,
+                grade: None,
+                started_at: 0,
+                ended_at: None,
+            });
+
+        if attempt == current {
+            if let Some(progress) = Self::get_progress(env, user, course_id) {
+                record.lessons_completed = progress.lessons_completed;
+                record.total_lessons = progress.total_lessons;
+                record.is_completed = progress.is_completed;
+            }
+        }
+
+        record
+    }
+
+    /// Get every attempt of a learner at a course, oldest first
+    pub fn get_attempts(env: Env, user: Address, course_id: String) -> Vec<AttemptRecord> {
+        let current = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+
+        let mut attempts = Vec::new(&env);
+        for attempt in 1..=current {
+            attempts.push_back(Self::get_attempt(env.clone(), user.clone(), course_id.clone(), attempt));
+        }
+
+        attempts
+    }
+
+    /// Get the attempt that counts under the course's attempt policy
+    pub fn get_counted_attempt(env: Env, user: Address, course_id: String) -> AttemptRecord {
+        let attempts = Self::get_attempts(env.clone(), user, course_id.clone());
+        let mut counted = attempts.get(attempts.len() - 1).unwrap();
+
+        if Self::get_attempt_policy(env, course_id).scoring == AttemptScoring::Best {
+            for attempt in attempts.iter() {
+                if (attempt.grade, attempt.is_completed, attempt.lessons_completed)
+                    > (counted.grade, counted.is_completed, counted.lessons_completed)
+                {
+                    counted = attempt;
+                }
+            }
+        }
+
+        counted
+    }
+
+    /// Set which lessons of a course need attested progress (course instructor only)
+    pub fn set_attestation_policy(
+        env: Env,
+        course_id: String,
+        mode: AttestationMode,
+        graded_lessons: Vec<u32>,
+        instructor_key: Option<BytesN<32>>,
+    ) {
+        let course = Self::get_active_course(env.clone(), course_id.clone());
+        course.instructor.require_auth();
+
+        for lesson_index in graded_lessons.iter() {
+            if lesson_index >= course.lesson_count {
+                panic!("Lesson index out of range");
+            }
+        }
+
+        let policy = AttestationPolicy {
+            mode,
+            graded_lessons,
+        };
+        env.storage().persistent().set(&ProgressKey::AttestationPolicy(course_id.clone()), &policy);
+
+        match instructor_key {
+            Some(instructor_key) => env.storage().persistent().set(&ProgressKey::InstructorKey(course_id), &instructor_key),
+            None => env.storage().persistent().remove(&ProgressKey::InstructorKey(course_id)),
+        }
+    }
+
+    /// Get the ed25519 key the course instructor signs attestations with, if set
+    pub fn get_instructor_key(env: Env, course_id: String) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&ProgressKey::InstructorKey(course_id))
+    }
+
+    /// Get the attestation policy of a course; courses without one are self-reported
+    pub fn get_attestation_policy(env: Env, course_id: String) -> AttestationPolicy {
+        env.storage().persistent()
+            .get(&ProgressKey::AttestationPolicy(course_id))
+            .unwrap_or_else(|| AttestationPolicy {
+                mode: AttestationMode::SelfReported,
+                graded_lessons: Vec::new(&env),
+            })
+    }
+
+    /// Register a grading oracle key trusted to attest lessons in any course (Admin only)
+    pub fn add_grading_oracle(env: Env, admin: Address, public_key: BytesN<32>) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&ProgressKey::GradingOracle(public_key), &true);
+    }
+
+    /// Remove a grading oracle key (Admin only)
+    pub fn remove_grading_oracle(env: Env, admin: Address, public_key: BytesN<32>) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().remove(&ProgressKey::GradingOracle(public_key));
+    }
+
+    /// Check whether a key is a registered grading oracle
+    pub fn is_grading_oracle(env: Env, public_key: BytesN<32>) -> bool {
+        env.storage().instance()
+            .get(&ProgressKey::GradingOracle(public_key))
+            .unwrap_or(false)
+    }
+
+    /// The bytes an attester signs for a lesson completion in a given attempt
+    pub fn attestation_payload(env: Env, user: Address, course_id: String, attempt: u32, lesson_index: u32) -> Bytes {
+        LessonAttestation {
+            contract: env.current_contract_address(),
+            user,
+            course_id,
+            attempt,
+            lesson_index,
+        }.to_xdr(&env)
+    }
+
+    /// Record a study session for a course, counting towards the learner's daily streak
+    pub fn record_study_session(env: Env, user: Address, course_id: String, duration_minutes: u32) -> StudyStats {
+        user.require_auth();
+        Self::get_active_course(env.clone(), course_id.clone());
+
+        if duration_minutes == 0 || duration_minutes > MAX_SESSION_MINUTES {
+            panic!("Session duration out of range");
+        }
+
+        let now = env.ledger().timestamp();
+        let mut stats = Self::load_study_stats(env.clone(), user.clone());
+        if stats.session_count > 0 && now - stats.last_session_at < duration_minutes as u64 * 60 {
+            panic!("Session overlaps the previous session");
+        }
+
+        stats = Self::mark_active_day(env.clone(), user.clone(), stats);
+        if stats.day_minutes + duration_minutes > MAX_DAILY_STUDY_MINUTES {
+            panic!("Daily study time limit reached");
+        }
+
+        stats.day_minutes += duration_minutes;
+        stats.total_minutes += duration_minutes as u64;
+        stats.session_count += 1;
+        stats.last_session_at = now;
+        env.storage().persistent().set(&ProgressKey::StudyStats(user.clone()), &stats);
+
+        let course_key = ProgressKey::CourseStudyMinutes(user, course_id);
+        let course_minutes: u64 = env.storage().persistent().get(&course_key).unwrap_or(0);
+        env.storage().persistent().set(&course_key, &(course_minutes + duration_minutes as u64));
+
+        stats
+    }
+
+    /// Get a learner's study time and streaks; the current streak is zero once a day is missed
+    pub fn get_study_stats(env: Env, user: Address) -> StudyStats {
+        let mut stats = Self::load_study_stats(env.clone(), user);
+        let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+        if today > stats.last_active_day + 1 {
+            stats.current_streak = 0;
+        }
+        if today != stats.last_active_day {
+            stats.day_minutes = 0;
+        }
+
+        stats
+    }
+
+    /// Get the minutes a learner has studied a course
+    pub fn get_course_study_minutes(env: Env, user: Address, course_id: String) -> u64 {
+        env.storage().persistent()
+            .get(&ProgressKey::CourseStudyMinutes(user, course_id))
+            .unwrap_or(0)
+    }
+
+    /// Set the streak lengths, in days, that earn a streak achievement (Admin only)
+    pub fn set_streak_thresholds(env: Env, admin: Address, thresholds: Vec<u32>) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&ProgressKey::StreakThresholds, &thresholds);
+    }
+
+    /// Get the configured streak achievement thresholds
+    pub fn get_streak_thresholds(env: Env) -> Vec<u32> {
+        env.storage().instance()
+            .get(&ProgressKey::StreakThresholds)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the streak thresholds a learner has been awarded
+    pub fn get_streak_awards(env: Env, user: Address) -> Vec<u32> {
+        env.storage().persistent()
+            .get(&ProgressKey::StreakAwards(user))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set the progress milestones of a course, in ascending order (course instructor only)
+    pub fn set_course_milestones(env: Env, course_id: String, milestones: Vec<Milestone>) {
+        let course = Self::get_active_course(env.clone(), course_id.clone());
+        course.instructor.require_auth();
+
+        let mut last_percent = 0;
+        for milestone in milestones.iter() {
+            if milestone.percent <= last_percent || milestone.percent > 100 {
+                panic!("Milestones must be ascending percentages up to 100");
+            }
+            last_percent = milestone.percent;
+        }
+
+        env.storage().persistent().set(&ProgressKey::CourseMilestones(course_id), &milestones);
+    }
+
+    /// Get the progress milestones of a course
+    pub fn get_course_milestones(env: Env, course_id: String) -> Vec<Milestone> {
+        env.storage().persistent()
+            .get(&ProgressKey::CourseMilestones(course_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set the EventLoggerContract that milestone hooks log achievements to (Admin only)
+    pub fn set_event_logger_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&ProgressKey::EventLoggerContract, &contract);
+    }
+
+    /// Set the AnalyticsContract that progress updates are reported to (Admin only)
+    pub fn set_analytics_contract(env: Env, admin: Address, contract: Address) {
+        Self::require_admin(env.clone(), admin);
+        env.storage().instance().set(&ProgressKey::AnalyticsContract, &contract);
+    }
+
+    /// Get up to `limit` of the courses a user has progress in, starting at `cursor`
+    pub fn get_user_courses(env: Env, user: Address, cursor: u32, limit: u32) -> Vec<String> {
+        let courses: Vec<String> = env.storage().persistent()
+            .get(&ProgressKey::UserCourses(user))
+            .unwrap_or_else(|| Vec::new(&env));
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(courses.len());
+
+        if cursor >= end {
+            Vec::new(&env)
+        } else {
+            courses.slice(cursor..end)
+        }
+    }
+
+    /// Get up to `limit` of the learners with progress in a course, starting at `cursor`
+    pub fn get_course_learners(env: Env, course_id: String, cursor: u32, limit: u32) -> Vec<Address> {
+        let learner_count = Self::get_course_stats(env.clone(), course_id.clone()).learners;
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(learner_count);
+
+        let mut learners = Vec::new(&env);
+        let mut chunk_index = u32::MAX;
+        let mut chunk: Vec<Address> = Vec::new(&env);
+        for index in cursor..end {
+            if index / HISTORY_CHUNK_SIZE != chunk_index {
+                chunk_index = index / HISTORY_CHUNK_SIZE;
+                chunk = env.storage().persistent()
+                    .get(&ProgressKey::CourseLearners(course_id.clone(), chunk_index))
+                    .unwrap_or_else(|| Vec::new(&env));
+            }
+            learners.push_back(chunk.get(index % HISTORY_CHUNK_SIZE).unwrap());
+        }
+
+        learners
+    }
+
+    /// Get learner counts and average progress for a course
+    pub fn get_course_stats(env: Env, course_id: String) -> CourseProgressStats {
+        let mut stats: CourseProgressStats = env.storage().persistent()
+            .get(&ProgressKey::CourseStats(course_id))
+            .unwrap_or(CourseProgressStats {
+                learners: 0,
+                active_learners: 0,
+                completions: 0,
+                total_progress_bps: 0,
+                avg_progress_bps: 0,
+            });
+
+        if stats.learners > 0 {
+            stats.avg_progress_bps = (stats.total_progress_bps / stats.learners as u64) as u32;
+        }
+
+        stats
+    }
+
+    pub fn get_progress(env: Env, user: Address, course_id: String) -> Option<UserProgress> {
+        env.storage().persistent().get(&ProgressKey::UserProgress(user, course_id))
+    }
+
+    /// Get the number of progress snapshots recorded for a (user, course, attempt)
+    pub fn get_history_length(env: Env, user: Address, course_id: String, attempt: u32) -> u32 {
+        env.storage().persistent()
+            .get(&ProgressKey::HistoryLength(user, course_id, attempt))
+            .unwrap_or(0)
+    }
+
+    /// Get up to `limit` progress snapshots of an attempt starting at `cursor`, oldest first
+    pub fn get_progress_history(env: Env, user: Address, course_id: String, attempt: u32, cursor: u32, limit: u32) -> Vec<ProgressSnapshot> {
+        let length = Self::get_history_length(env.clone(), user.clone(), course_id.clone(), attempt);
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(length);
+
+        let mut snapshots = Vec::new(&env);
+        let mut chunk_index = u32::MAX;
+        let mut chunk: Vec<ProgressSnapshot> = Vec::new(&env);
+        for index in cursor..end {
+            if index / HISTORY_CHUNK_SIZE != chunk_index {
+                chunk_index = index / HISTORY_CHUNK_SIZE;
+                chunk = env.storage().persistent()
+                    .get(&ProgressKey::HistoryChunk(user.clone(), course_id.clone(), attempt, chunk_index))
+                    .unwrap_or_else(|| Vec::new(&env));
+            }
+            snapshots.push_back(chunk.get(index % HISTORY_CHUNK_SIZE).unwrap());
+        }
+
+        snapshots
+    }
+
+    /// Get the learning pace of an attempt in hundredths of a lesson per week, from its first to its latest snapshot
+    pub fn get_pace(env: Env, user: Address, course_id: String, attempt: u32) -> u64 {
+        let length = Self::get_history_length(env.clone(), user.clone(), course_id.clone(), attempt);
+        if length < 2 {
+            return 0;
+        }
+
+        let first = Self::get_snapshot(env.clone(), user.clone(), course_id.clone(), attempt, 0);
+        let latest = Self::get_snapshot(env, user, course_id, attempt, length - 1);
+        let elapsed = latest.timestamp.saturating_sub(first.timestamp);
+        if elapsed == 0 {
+            return 0;
+        }
+
+        let lessons = latest.lessons_completed.saturating_sub(first.lessons_completed) as u64;
+        lessons * 100 * SECONDS_PER_WEEK / elapsed
+    }
+
+    /// Get the seconds between the first recorded progress of an attempt and completing the course in it
+    pub fn get_time_to_completion(env: Env, user: Address, course_id: String, attempt: u32) -> Option<u64> {
+        let completed_at: u64 = env.storage().persistent()
+            .get(&ProgressKey::CompletedAt(user.clone(), course_id.clone(), attempt))?;
+        let first = Self::get_snapshot(env, user, course_id, attempt, 0);
+
+        Some(completed_at - first.timestamp)
+    }
+
+    /// Get the indices of the lessons a user has completed, in ascending order
+    pub fn get_completed_lessons(env: Env, user: Address, course_id: String) -> Vec<u32> {
+        let bitmap = Self::get_bitmap(env.clone(), user, course_id);
+
+        let mut lessons = Vec::new(&env);
+        for (word_index, word) in bitmap.iter().enumerate() {
+            for bit in 0..64 {
+                if word & (1u64 << bit) != 0 {
+                    lessons.push_back(word_index as u32 * 64 + bit);
+                }
+            }
+        }
+
+        lessons
+    }
+
+    /// Get the lessons a user completed after their cohort due dates during an attempt
+    pub fn get_late_lessons(env: Env, user: Address, course_id: String, attempt: u32) -> Vec<LateLesson> {
+        env.storage().persistent()
+            .get(&ProgressKey::LateLessons(user, course_id, attempt))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Check whether a user has completed a lesson
+    pub fn is_lesson_completed(env: Env, user: Address, course_id: String, lesson_index: u32) -> bool {
+        let bitmap = Self::get_bitmap(env, user, course_id);
+        Self::bit_is_set(&bitmap, lesson_index)
+    }
+
+    /// Flag newly completed lessons that missed their cohort due date, rejecting any past the hard cutoff
+    fn flag_late_lessons(env: Env, user: Address, course_id: String, lesson_indexes: Vec<u32>) {
+        let course_contract: Address = env.storage().instance()
+            .get(&ProgressKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+
+        // Self-paced learners have no deadlines
+        let cohort = match CourseMetadataContractClient::new(&env, &course_contract).get_learner_cohort(&course_id, &user) {
+            Some(cohort) => cohort,
+            None => return,
+        };
+
+        let now = env.ledger().timestamp();
+        let attempt = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+        let mut late_lessons = Self::get_late_lessons(env.clone(), user.clone(), course_id.clone(), attempt);
+        let flagged = late_lessons.len();
+        for lesson_index in lesson_indexes.iter() {
+            let status = cohort.deadline_status(Some(lesson_index), now);
+            if status == DeadlineStatus::PastCutoff {
+                panic!("Submission is past the cohort cutoff");
+            }
+            if status != DeadlineStatus::OnTime {
+                late_lessons.push_back(LateLesson { lesson_index, status, recorded_at: now });
+            }
+        }
+
+        if late_lessons.len() > flagged {
+            env.storage().persistent().set(&ProgressKey::LateLessons(user, course_id, attempt), &late_lessons);
+        }
+    }
+
+    /// Load the completion bitmap for a (user, course), one bit per lesson
+    fn get_bitmap(env: Env, user: Address, course_id: String) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&ProgressKey::LessonBitmap(user, course_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set the bit for a lesson, growing the bitmap as needed
+    fn set_lesson_bit(bitmap: &mut Vec<u64>, lesson_index: u32) {
+        let word_index = lesson_index / 64;
+        while bitmap.len() <= word_index {
+            bitmap.push_back(0);
+        }
+
+        let word = bitmap.get(word_index).unwrap_or(0);
+        bitmap.set(word_index, word | (1u64 << (lesson_index % 64)));
+    }
+
+    /// Clear the bits for lessons at or beyond the course's lesson count
+    fn mask_lessons(env: &Env, bitmap: Vec<u64>, total_lessons: u32) -> Vec<u64> {
+        let mut masked = Vec::new(env);
+        for (word_index, word) in bitmap.iter().enumerate() {
+            let first_lesson = word_index as u32 * 64;
+            if first_lesson >= total_lessons {
+                break;
+            }
+            let remaining = total_lessons - first_lesson;
+            masked.push_back(if remaining >= 64 { word } else { word & ((1u64 << remaining) - 1) });
+        }
+        masked
+    }
+
+    /// Check the bit for a lesson
+    fn bit_is_set(bitmap: &Vec<u64>, lesson_index: u32) -> bool {
+        match bitmap.get(lesson_index / 64) {
+            Some(word) => word & (1u64 << (lesson_index % 64)) != 0,
+            None => false,
+        }
+    }
+
+    /// Check whether a lesson's completion must be attested under a policy
+    fn requires_attestation(policy: &AttestationPolicy, lesson_index: u32) -> bool {
+        match policy.mode {
+            AttestationMode::SelfReported => false,
+            AttestationMode::GradedLessons => policy.graded_lessons.contains(lesson_index),
+            AttestationMode::AllLessons => true,
+        }
+    }
+
+    /// Require authorization from the admin address
+    fn require_admin(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance()
+            .get(&ProgressKey::Admin)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+
+        if caller != admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+
+    /// Store the bitmap and the progress derived from it
+    fn save_progress(
+        env: Env,
+        user: Address,
+        course_id: String,
+        bitmap: Vec<u64>,
+        total_lessons: u32,
+        required_lessons: Vec<u32>,
+    ) -> UserProgress {
+        // Lessons beyond a shortened course no longer count
+        let bitmap = Self::mask_lessons(&env, bitmap, total_lessons);
+        let lessons_completed: u32 = bitmap.iter().map(|word| word.count_ones()).sum();
+
+        // Courses with required lessons are complete once those are done; otherwise every lesson counts
+        let is_completed = if required_lessons.is_empty() {
+            lessons_completed >= total_lessons
+        } else {
+            required_lessons.iter().all(|lesson_index| Self::bit_is_set(&bitmap, lesson_index))
+        };
 
         let progress = UserProgress {
             user: user.clone(),
@@ -41,10 +876,278 @@ impl CourseProgressContract {
             last_updated: env.ledger().timestamp(),
         };
 
+        let previous = Self::get_progress(env.clone(), user.clone(), course_id.clone());
+        let changed = match &previous {
+            Some(previous) => {
+                previous.lessons_completed != lessons_completed
+                    || previous.total_lessons != total_lessons
+                    || previous.is_completed != is_completed
+            }
+            None => true,
+        };
+        let attempt = Self::get_attempt_number(env.clone(), user.clone(), course_id.clone());
+        if changed {
+            Self::append_snapshot(env.clone(), user.clone(), course_id.clone(), attempt, ProgressSnapshot {
+                lessons_completed,
+                total_lessons,
+                timestamp: progress.last_updated,
+            });
+
+            let previous_percent = previous.clone().map(|previous| Self::percent_complete(&previous)).unwrap_or(0);
+            Self::trigger_milestones(env.clone(), user.clone(), course_id.clone(), attempt, previous_percent, Self::percent_complete(&progress));
+            Self::update_course_stats(env.clone(), user.clone(), course_id.clone(), previous, &progress);
+        }
+
+        let completed_key = ProgressKey::CompletedAt(user.clone(), course_id.clone(), attempt);
+        if progress.is_completed && !env.storage().persistent().has(&completed_key) {
+            env.storage().persistent().set(&completed_key, &progress.last_updated);
+        }
+
+        let stats = Self::mark_active_day(env.clone(), user.clone(), Self::load_study_stats(env.clone(), user.clone()));
+        env.storage().persistent().set(&ProgressKey::StudyStats(user.clone()), &stats);
+
+        env.storage().persistent().set(&ProgressKey::LessonBitmap(user.clone(), course_id.clone()), &bitmap);
         env.storage().persistent().set(&ProgressKey::UserProgress(user, course_id), &progress);
+
+        progress
     }
 
-    pub fn get_progress(env: Env, user: Address, course_id: String) -> Option<UserProgress> {
-        env.storage().persistent().get(&ProgressKey::UserProgress(user, course_id))
+    /// Progress in basis points (0-10000); 10000 once the course counts as completed
+    fn progress_bps(progress: &UserProgress) -> u64 {
+        if progress.is_completed {
+            10000
+        } else {
+            progress.lessons_completed as u64 * 10000 / progress.total_lessons as u64
+        }
+    }
+
+    /// Keep the learner indices and course aggregates in step with a progress update
+    fn update_course_stats(
+        env: Env,
+        user: Address,
+        course_id: String,
+        previous: Option<UserProgress>,
+        progress: &UserProgress,
+    ) {
+        let mut stats = Self::get_course_stats(env.clone(), course_id.clone());
+
+        match &previous {
+            Some(previous) => {
+                stats.total_progress_bps = stats.total_progress_bps.saturating_sub(Self::progress_bps(previous));
+            }
+            None => {
+                let mut user_courses: Vec<String> = env.storage().persistent()
+                    .get(&ProgressKey::UserCourses(user.clone()))
+                    .unwrap_or_else(|| Vec::new(&env));
+                user_courses.push_back(course_id.clone());
+                env.storage().persistent().set(&ProgressKey::UserCourses(user.clone()), &user_courses);
+
+                let chunk_key = ProgressKey::CourseLearners(course_id.clone(), stats.learners / HISTORY_CHUNK_SIZE);
+                let mut chunk: Vec<Address> = env.storage().persistent()
+                    .get(&chunk_key)
+                    .unwrap_or_else(|| Vec::new(&env));
+                chunk.push_back(user.clone());
+                env.storage().persistent().set(&chunk_key, &chunk);
+
+                stats.learners = stats.learners.saturating_add(1);
+                stats.active_learners = stats.active_learners.saturating_add(1);
+            }
+        }
+
+        if let Some(analytics) = env.storage().instance().get::<_, Address>(&ProgressKey::AnalyticsContract) {
+            let course = Self::get_active_course(env.clone(), course_id.clone());
+            AnalyticsContractClient::new(&env, &analytics).on_progress(
+                &user,
+                &course_id,
+                &course.instructor,
+                &course.category,
+                &previous.as_ref().map(|previous| Self::progress_bps(previous) as u32),
+                &(Self::progress_bps(progress) as u32),
+            );
+        }
+
+        let was_completed = previous.map(|previous| previous.is_completed).unwrap_or(false);
+        if progress.is_completed && !was_completed {
+            stats.completions = stats.completions.saturating_add(1);
+            stats.active_learners = stats.active_learners.saturating_sub(1);
+        } else if !progress.is_completed && was_completed {
+            // A course gaining required lessons can reopen it
+            stats.completions = stats.completions.saturating_sub(1);
+            stats.active_learners = stats.active_learners.saturating_add(1);
+        }
+        stats.total_progress_bps = stats.total_progress_bps.saturating_add(Self::progress_bps(progress));
+
+        env.storage().persistent().set(&ProgressKey::CourseStats(course_id), &stats);
+    }
+
+    /// Whole percentage of a course completed; 100 once the course counts as completed
+    fn percent_complete(progress: &UserProgress) -> u32 {
+        if progress.is_completed {
+            100
+        } else {
+            progress.lessons_completed * 100 / progress.total_lessons
+        }
     }
-}
\ No newline at end of file
+
+    /// Publish an event, and run the milestone's hook, for every milestone crossed
+    fn trigger_milestones(env: Env, user: Address, course_id: String, attempt: u32, previous_percent: u32, percent: u32) {
+        let reached_key = ProgressKey::MilestonesReached(user.clone(), course_id.clone(), attempt);
+        let mut reached: Vec<u32> = env.storage().persistent()
+            .get(&reached_key)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        for milestone in Self::get_course_milestones(env.clone(), course_id.clone()).iter() {
+            if milestone.percent <= previous_percent || milestone.percent > percent || reached.contains(milestone.percent) {
+                continue;
+            }
+            reached.push_back(milestone.percent);
+
+            env.events().publish(
+                (symbol_short!("milestone"), course_id.clone()),
+                (user.clone(), attempt, milestone.percent)
+            );
+
+            if milestone.hook == MilestoneHook::LogAchievement {
+                if let Some(event_logger) = env.storage().instance().get::<_, Address>(&ProgressKey::EventLoggerContract) {
+                    EventLoggerContractClient::new(&env, &event_logger).log_user_achievement(
+                        &user,
+                        &Self::milestone_achievement_type(env.clone(), milestone.percent),
+                        &course_id,
+                    );
+                }
+            }
+        }
+
+        env.storage().persistent().set(&reached_key, &reached);
+    }
+
+    /// Get the milestone percentages a learner has reached in an attempt, in the order reached
+    pub fn get_milestones_reached(env: Env, user: Address, course_id: String, attempt: u32) -> Vec<u32> {
+        env.storage().persistent()
+            .get(&ProgressKey::MilestonesReached(user, course_id, attempt))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Achievement type for a milestone, e.g. "milestone_50"
+    fn milestone_achievement_type(env: Env, percent: u32) -> String {
+        let mut buf = *b"milestone_000";
+        let mut len = buf.len();
+        let mut digits = 0;
+        let mut remaining = percent;
+        loop {
+            digits += 1;
+            buf[10 + 3 - digits] = b'0' + (remaining % 10) as u8;
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+        buf.copy_within(13 - digits..13, 10);
+        len -= 3 - digits;
+
+        String::from_bytes(&env, &buf[..len])
+    }
+
+    fn load_study_stats(env: Env, user: Address) -> StudyStats {
+        env.storage().persistent()
+            .get(&ProgressKey::StudyStats(user))
+            .unwrap_or(StudyStats {
+                total_minutes: 0,
+                session_count: 0,
+                current_streak: 0,
+                longest_streak: 0,
+                last_active_day: 0,
+                day_minutes: 0,
+                last_session_at: 0,
+            })
+    }
+
+    /// Count today towards the learner's streak and award any streak thresholds reached
+    fn mark_active_day(env: Env, user: Address, mut stats: StudyStats) -> StudyStats {
+        let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+        if stats.current_streak > 0 && today == stats.last_active_day {
+            return stats;
+        }
+
+        if stats.current_streak > 0 && today == stats.last_active_day + 1 {
+            stats.current_streak += 1;
+        } else {
+            stats.current_streak = 1;
+        }
+        stats.last_active_day = today;
+        stats.day_minutes = 0;
+        if stats.current_streak > stats.longest_streak {
+            stats.longest_streak = stats.current_streak;
+        }
+
+        if Self::get_streak_thresholds(env.clone()).contains(stats.current_streak) {
+            let mut awards = Self::get_streak_awards(env.clone(), user.clone());
+            if !awards.contains(stats.current_streak) {
+                awards.push_back(stats.current_streak);
+                env.storage().persistent().set(&ProgressKey::StreakAwards(user.clone()), &awards);
+                env.events().publish(
+                    (symbol_short!("streak"), symbol_short!("awarded")),
+                    (user, stats.current_streak)
+                );
+            }
+        }
+
+        stats
+    }
+
+    /// Append a snapshot to the history, starting a new chunk when the current one is full
+    fn append_snapshot(env: Env, user: Address, course_id: String, attempt: u32, snapshot: ProgressSnapshot) {
+        let length = Self::get_history_length(env.clone(), user.clone(), course_id.clone(), attempt);
+        let chunk_key = ProgressKey::HistoryChunk(user.clone(), course_id.clone(), attempt, length / HISTORY_CHUNK_SIZE);
+
+        let mut chunk: Vec<ProgressSnapshot> = env.storage().persistent()
+            .get(&chunk_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        chunk.push_back(snapshot);
+
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&ProgressKey::HistoryLength(user, course_id, attempt), &(length + 1));
+    }
+
+    /// Get the snapshot at a position in an attempt's history
+    fn get_snapshot(env: Env, user: Address, course_id: String, attempt: u32, index: u32) -> ProgressSnapshot {
+        let chunk: Vec<ProgressSnapshot> = env.storage().persistent()
+            .get(&ProgressKey::HistoryChunk(user, course_id, attempt, index / HISTORY_CHUNK_SIZE))
+            .unwrap_or_else(|| panic!("Snapshot not found"));
+
+        chunk.get(index % HISTORY_CHUNK_SIZE).unwrap_or_else(|| panic!("Snapshot not found"))
+    }
+
+    /// Look up a course, rejecting unknown or inactive courses
+    fn get_active_course(env: Env, course_id: String) -> CourseMetadata {
+        let course_contract: Address = env.storage().instance()
+            .get(&ProgressKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+
+        let course = match CourseMetadataContractClient::new(&env, &course_contract).try_get_course(&course_id) {
+            Ok(Ok(course)) => course,
+            _ => panic!("Course not found"),
+        };
+
+        if course.status != CourseStatus::Active {
+            panic!("Course is not active");
+        }
+
+        course
+    }
+
+    /// Look up a course's lesson count and required lessons, rejecting unknown or inactive courses
+    fn course_lessons(env: Env, course_id: String) -> (u32, Vec<u32>) {
+        let course = Self::get_active_course(env.clone(), course_id.clone());
+        if course.lesson_count == 0 || course.lesson_count > MAX_LESSONS {
+            panic!("Course lesson count out of range");
+        }
+
+        let course_contract: Address = env.storage().instance()
+            .get(&ProgressKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        let required_lessons = CourseMetadataContractClient::new(&env, &course_contract).get_required_lessons(&course_id);
+
+        (course.lesson_count, required_lessons)
+    }
+}
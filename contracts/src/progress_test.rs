@@ -1,35 +1,754 @@
 #![cfg(test)]
+extern crate std;
 
-use crate::progress::{CourseProgressContract, CourseProgressContractClient};
-use soroban_sdk::{Env, testutils::{Address as _, Ledger}, Address, String};
+use crate::progress::{AttemptScoring, AttestationMode, CourseProgressContract, CourseProgressContractClient, Milestone, MilestoneHook};
+use crate::eventLogger::{EventLoggerContract, EventLoggerContractClient};
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, CourseStatus, CourseUpdate, DeadlineStatus, GradeComponent, GradeComponentKind, GradeRounding, LatePolicy, NewCourse};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{Env, testutils::{Address as _, Ledger}, vec, Address, BytesN, String};
+
+/// Register the progress contract against a metadata contract holding one active course
+fn setup<'a>(env: &Env, lesson_count: u32) -> (CourseProgressContractClient<'a>, CourseMetadataContractClient<'a>, String, Address, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let instructor = Address::generate(env);
+
+    let courses_id = env.register_contract(None, CourseMetadataContract);
+    let courses = CourseMetadataContractClient::new(env, &courses_id);
+    courses.initialize(&admin);
+
+    let course_id = courses.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(env, "Soroban Basics"),
+            description: String::from_str(env, "Smart contracts on Stellar"),
+            category: String::from_str(env, "Programming"),
+            level: String::from_str(env, "beginner"),
+            duration: 10,
+            price: 0,
+            prerequisites: vec![env],
+            learning_objectives: vec![env],
+            syllabus: String::from_str(env, "QmSyllabus"),
+            thumbnail_url: String::from_str(env, "https://example.com/thumbnail.jpg"),
+            tags: vec![env],
+            language: String::from_str(env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+    courses.set_course_lessons(&course_id, &instructor, &lesson_count);
+
+    let contract_id = env.register_contract(None, CourseProgressContract);
+    let client = CourseProgressContractClient::new(env, &contract_id);
+    client.initialize(&admin, &courses_id);
+
+    (client, courses, course_id, instructor, admin)
+}
 
 #[test]
 fn test_progress_tracking() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, CourseProgressContract);
-    let client = CourseProgressContractClient::new(&env, &contract_id);
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 10);
 
     let user = Address::generate(&env);
-    let course_id = String::from_str(&env, "course-101");
-
-    // Mock authentication
-    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
 
     // Record initial progress
-    client.record_progress(&user, &course_id, &2, &10);
+    client.record_progress(&user, &course_id, &2);
 
     // Verify progress stored
     let progress = client.get_progress(&user, &course_id).unwrap();
     assert_eq!(progress.lessons_completed, 2);
     assert_eq!(progress.total_lessons, 10);
-    assert_eq!(progress.is_completed, false);
+    assert!(!progress.is_completed);
 
     // Update progress to completion
-    client.record_progress(&user, &course_id, &10, &10);
+    client.record_progress(&user, &course_id, &10);
 
     // Verify completion
     let completed_progress = client.get_progress(&user, &course_id).unwrap();
     assert_eq!(completed_progress.lessons_completed, 10);
-    assert_eq!(completed_progress.is_completed, true);
+    assert!(completed_progress.is_completed);
     assert!(completed_progress.last_updated > 0);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_lesson_completion_bitmap() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 70);
+
+    let user = Address::generate(&env);
+
+    // Complete lessons out of order, including one past the first bitmap word
+    client.complete_lesson(&user, &course_id, &3);
+    client.complete_lesson(&user, &course_id, &65);
+    let progress = client.complete_lesson(&user, &course_id, &3);
+
+    // Repeating a lesson does not count twice
+    assert_eq!(progress.lessons_completed, 2);
+    assert!(!progress.is_completed);
+
+    let lessons = client.get_completed_lessons(&user, &course_id);
+    assert_eq!(lessons.len(), 2);
+    assert_eq!(lessons.get(0).unwrap(), 3);
+    assert_eq!(lessons.get(1).unwrap(), 65);
+
+    assert!(client.is_lesson_completed(&user, &course_id, &65));
+    assert!(!client.is_lesson_completed(&user, &course_id, &64));
+}
+
+#[test]
+fn test_shortened_course_drops_stale_lessons() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, _admin) = setup(&env, 70);
+
+    let user = Address::generate(&env);
+    client.complete_lesson(&user, &course_id, &3);
+    client.complete_lesson(&user, &course_id, &65);
+
+    // Lessons past the new end of the course stop counting
+    courses.set_course_lessons(&course_id, &instructor, &10);
+    let progress = client.complete_lesson(&user, &course_id, &5);
+    assert_eq!(progress.lessons_completed, 2);
+    assert_eq!(progress.total_lessons, 10);
+    assert!(!progress.is_completed);
+
+    // Growing the course again does not bring them back
+    courses.set_course_lessons(&course_id, &instructor, &70);
+    assert!(!client.is_lesson_completed(&user, &course_id, &65));
+    client.record_progress(&user, &course_id, &2);
+}
+
+#[test]
+#[should_panic(expected = "Lessons completed exceeds total lessons")]
+fn test_progress_beyond_course_length_rejected() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 10);
+
+    client.record_progress(&Address::generate(&env), &course_id, &11);
+}
+
+#[test]
+#[should_panic(expected = "Progress cannot go backwards")]
+fn test_progress_regression_rejected() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 10);
+    let user = Address::generate(&env);
+
+    client.record_progress(&user, &course_id, &5);
+    client.record_progress(&user, &course_id, &4);
+}
+
+#[test]
+#[should_panic(expected = "Course not found")]
+fn test_progress_unknown_course_rejected() {
+    let env = Env::default();
+    let (client, _courses, _course_id, _instructor, _admin) = setup(&env, 10);
+
+    client.record_progress(&Address::generate(&env), &String::from_str(&env, "course-404"), &1);
+}
+
+#[test]
+#[should_panic(expected = "Course is not active")]
+fn test_progress_inactive_course_rejected() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, _admin) = setup(&env, 10);
+
+    courses.update_course(
+        &course_id,
+        &instructor,
+        &CourseUpdate {
+            title: None,
+            description: None,
+            category: None,
+            level: None,
+            duration: None,
+            price: None,
+            prerequisites: None,
+            learning_objectives: None,
+            syllabus: None,
+            thumbnail_url: None,
+            tags: None,
+            language: None,
+            certificate_enabled: None,
+            max_students: None,
+        },
+        &Some(CourseStatus::Archived),
+    );
+
+    client.record_progress(&Address::generate(&env), &course_id, &1);
+}
+
+#[test]
+fn test_attested_lesson_progress() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 5);
+    let user = Address::generate(&env);
+
+    let instructor_key = SigningKey::from_bytes(&[7; 32]);
+    let instructor_public = BytesN::from_array(&env, &instructor_key.verifying_key().to_bytes());
+
+    // Lesson 2 is a graded assignment
+    client.set_attestation_policy(&course_id, &AttestationMode::GradedLessons, &vec![&env, 2], &Some(instructor_public.clone()));
+
+    // Ungraded lessons can still be self-reported
+    client.complete_lesson(&user, &course_id, &0);
+
+    let payload = client.attestation_payload(&user, &course_id, &1, &2);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&env, &instructor_key.sign(&message).to_bytes());
+
+    let progress = client.complete_attested_lesson(&user, &course_id, &2, &instructor_public, &signature);
+    assert_eq!(progress.lessons_completed, 2);
+    assert!(client.is_lesson_completed(&user, &course_id, &2));
+}
+
+#[test]
+fn test_grading_oracle_attestation() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, admin) = setup(&env, 5);
+    let user = Address::generate(&env);
+
+    let oracle_key = SigningKey::from_bytes(&[9; 32]);
+    let oracle_public = BytesN::from_array(&env, &oracle_key.verifying_key().to_bytes());
+    client.add_grading_oracle(&admin, &oracle_public);
+    client.set_attestation_policy(&course_id, &AttestationMode::AllLessons, &vec![&env], &None);
+
+    let payload = client.attestation_payload(&user, &course_id, &1, &4);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&env, &oracle_key.sign(&message).to_bytes());
+
+    client.complete_attested_lesson(&user, &course_id, &4, &oracle_public, &signature);
+    assert!(client.is_lesson_completed(&user, &course_id, &4));
+}
+
+#[test]
+#[should_panic(expected = "Lesson requires attestation")]
+fn test_graded_lesson_self_report_rejected() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 5);
+    let instructor_key = SigningKey::from_bytes(&[7; 32]);
+    let instructor_public = BytesN::from_array(&env, &instructor_key.verifying_key().to_bytes());
+
+    client.set_attestation_policy(&course_id, &AttestationMode::GradedLessons, &vec![&env, 2], &Some(instructor_public));
+
+    // Bulk progress cannot skip over the graded lesson either
+    client.record_progress(&Address::generate(&env), &course_id, &3);
+}
+
+#[test]
+#[should_panic(expected = "Unknown attestation key")]
+fn test_unknown_attestation_key_rejected() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 5);
+    let user = Address::generate(&env);
+    let rogue_key = SigningKey::from_bytes(&[3; 32]);
+    let rogue_public = BytesN::from_array(&env, &rogue_key.verifying_key().to_bytes());
+
+    let payload = client.attestation_payload(&user, &course_id, &1, &1);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&env, &rogue_key.sign(&message).to_bytes());
+
+    client.complete_attested_lesson(&user, &course_id, &1, &rogue_public, &signature);
+}
+
+#[test]
+fn test_progress_history() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 60);
+    let user = Address::generate(&env);
+
+    // One lesson a day for 60 days, spilling into a second history chunk
+    for day in 0..60u32 {
+        env.ledger().set_timestamp(1000 + day as u64 * 24 * 60 * 60);
+        client.complete_lesson(&user, &course_id, &day);
+    }
+
+    // Repeating a lesson does not add a snapshot
+    client.complete_lesson(&user, &course_id, &0);
+    assert_eq!(client.get_history_length(&user, &course_id, &1), 60);
+
+    // Pages cross chunk boundaries
+    let page = client.get_progress_history(&user, &course_id, &1, &45, &10);
+    assert_eq!(page.len(), 10);
+    assert_eq!(page.get(0).unwrap().lessons_completed, 46);
+    assert_eq!(page.get(9).unwrap().lessons_completed, 55);
+    assert_eq!(client.get_progress_history(&user, &course_id, &1, &58, &10).len(), 2);
+
+    // 59 lessons over 59 days is 7 lessons per week
+    assert_eq!(client.get_pace(&user, &course_id, &1), 700);
+    assert_eq!(client.get_time_to_completion(&user, &course_id, &1), Some(59 * 24 * 60 * 60));
+}
+
+#[test]
+fn test_time_to_completion_unfinished() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 10);
+    let user = Address::generate(&env);
+
+    client.record_progress(&user, &course_id, &3);
+    assert_eq!(client.get_time_to_completion(&user, &course_id, &1), None);
+    assert_eq!(client.get_pace(&user, &course_id, &1), 0);
+}
+
+#[test]
+fn test_curriculum_required_lessons() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, _admin) = setup(&env, 0);
+    let user = Address::generate(&env);
+
+    let basics = courses.add_module(&course_id, &instructor, &String::from_str(&env, "Basics"));
+    let extras = courses.add_module(&course_id, &instructor, &String::from_str(&env, "Extras"));
+    let intro = courses.add_lesson(
+        &course_id, &instructor, &basics, &String::from_str(&env, "Intro"),
+        &String::from_str(&env, "QmIntro"), &true, &15,
+    );
+    let storage = courses.add_lesson(
+        &course_id, &instructor, &basics, &String::from_str(&env, "Storage"),
+        &String::from_str(&env, "QmStorage"), &true, &30,
+    );
+    let history = courses.add_lesson(
+        &course_id, &instructor, &extras, &String::from_str(&env, "History of Stellar"),
+        &String::from_str(&env, "QmHistory"), &false, &20,
+    );
+
+    let curriculum = courses.get_curriculum(&course_id);
+    assert_eq!(curriculum.len(), 2);
+    assert_eq!(curriculum.get(0).unwrap().lesson_ids, vec![&env, intro, storage]);
+    assert_eq!(courses.get_course(&course_id).lesson_count, 3);
+    assert_eq!(courses.get_required_lessons(&course_id), vec![&env, intro, storage]);
+
+    // Lesson ids beyond the curriculum are rejected by progress
+    assert!(client.try_complete_lesson(&user, &course_id, &3).is_err());
+
+    // The optional lesson alone does not complete the course
+    let progress = client.complete_lesson(&user, &course_id, &history);
+    assert!(!progress.is_completed);
+    client.complete_lesson(&user, &course_id, &intro);
+    let progress = client.complete_lesson(&user, &course_id, &storage);
+    assert!(progress.is_completed);
+
+    // Reorder modules and make the optional lesson required
+    courses.reorder_modules(&course_id, &instructor, &vec![&env, extras, basics]);
+    assert_eq!(courses.get_curriculum(&course_id).get(0).unwrap().id, extras);
+    courses.update_lesson(&course_id, &instructor, &history, &None, &None, &Some(true), &None);
+    assert_eq!(courses.get_required_lessons(&course_id).len(), 3);
+
+    // Lessons still land in the module they name after a reorder
+    let review = courses.add_lesson(
+        &course_id, &instructor, &basics, &String::from_str(&env, "Review"),
+        &String::from_str(&env, "QmReview"), &false, &5,
+    );
+    let curriculum = courses.get_curriculum(&course_id);
+    assert_eq!(curriculum.get(1).unwrap().id, basics);
+    assert_eq!(curriculum.get(1).unwrap().lesson_ids.last(), Some(review));
+    assert!(!curriculum.get(0).unwrap().lesson_ids.contains(review));
+}
+
+#[test]
+fn test_study_streaks() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, admin) = setup(&env, 10);
+    let user = Address::generate(&env);
+    let day = 24 * 60 * 60;
+
+    client.set_streak_thresholds(&admin, &vec![&env, 3]);
+
+    // Three consecutive days of study
+    for offset in 0..3u64 {
+        env.ledger().set_timestamp(10 * day + offset * day + 3600);
+        client.record_study_session(&user, &course_id, &45);
+    }
+
+    let stats = client.get_study_stats(&user);
+    assert_eq!(stats.current_streak, 3);
+    assert_eq!(stats.longest_streak, 3);
+    assert_eq!(stats.total_minutes, 135);
+    assert_eq!(stats.session_count, 3);
+    assert_eq!(client.get_course_study_minutes(&user, &course_id), 135);
+    assert_eq!(client.get_streak_awards(&user), vec![&env, 3]);
+
+    // Completing a lesson the next day extends the streak
+    env.ledger().set_timestamp(13 * day + 3600);
+    client.complete_lesson(&user, &course_id, &0);
+    assert_eq!(client.get_study_stats(&user).current_streak, 4);
+
+    // Missing a day breaks it, but the longest streak is kept
+    env.ledger().set_timestamp(15 * day + 3600);
+    assert_eq!(client.get_study_stats(&user).current_streak, 0);
+    client.record_study_session(&user, &course_id, &30);
+    let stats = client.get_study_stats(&user);
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.longest_streak, 4);
+}
+
+#[test]
+#[should_panic(expected = "Session overlaps the previous session")]
+fn test_study_session_rate_limited() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 10);
+    let user = Address::generate(&env);
+
+    env.ledger().set_timestamp(100_000);
+    client.record_study_session(&user, &course_id, &60);
+
+    // Only 30 minutes have passed, so a 60 minute session cannot fit
+    env.ledger().set_timestamp(100_000 + 30 * 60);
+    client.record_study_session(&user, &course_id, &60);
+}
+
+#[test]
+fn test_progress_milestones() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    let logger_id = env.register_contract(None, EventLoggerContract);
+    let logger = EventLoggerContractClient::new(&env, &logger_id);
+    logger.initialize(&admin);
+    client.set_event_logger_contract(&admin, &logger_id);
+
+    client.set_course_milestones(&course_id, &vec![
+        &env,
+        Milestone { percent: 25, hook: MilestoneHook::EventOnly },
+        Milestone { percent: 50, hook: MilestoneHook::LogAchievement },
+        Milestone { percent: 75, hook: MilestoneHook::EventOnly },
+        Milestone { percent: 100, hook: MilestoneHook::LogAchievement },
+    ]);
+
+    // Jumping to 75% crosses three milestones but only one logs an achievement
+    client.record_progress(&user, &course_id, &3);
+    let events = logger.get_user_events(&user);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events.get(0).unwrap().achievement_type, Some(String::from_str(&env, "milestone_50")));
+
+    // Repeating a lesson crosses nothing new
+    client.complete_lesson(&user, &course_id, &0);
+    assert_eq!(logger.get_user_events(&user).len(), 1);
+
+    client.complete_lesson(&user, &course_id, &3);
+    let events = logger.get_user_events(&user);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events.get(1).unwrap().achievement_type, Some(String::from_str(&env, "milestone_100")));
+}
+
+#[test]
+#[should_panic(expected = "Milestones must be ascending percentages up to 100")]
+fn test_unordered_milestones_rejected() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 4);
+
+    client.set_course_milestones(&course_id, &vec![
+        &env,
+        Milestone { percent: 50, hook: MilestoneHook::EventOnly },
+        Milestone { percent: 25, hook: MilestoneHook::EventOnly },
+    ]);
+}
+
+#[test]
+fn test_course_learners_and_stats() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 4);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.record_progress(&first, &course_id, &2);
+    client.record_progress(&second, &course_id, &1);
+    client.record_progress(&first, &course_id, &4);
+
+    assert_eq!(client.get_user_courses(&first, &0, &10), vec![&env, course_id.clone()]);
+    assert_eq!(client.get_course_learners(&course_id, &0, &10), vec![&env, first.clone(), second.clone()]);
+    assert_eq!(client.get_course_learners(&course_id, &1, &10), vec![&env, second.clone()]);
+    assert_eq!(client.get_course_learners(&course_id, &2, &10).len(), 0);
+
+    let stats = client.get_course_stats(&course_id);
+    assert_eq!(stats.learners, 2);
+    assert_eq!(stats.active_learners, 1);
+    assert_eq!(stats.completions, 1);
+    // (100% + 25%) / 2
+    assert_eq!(stats.avg_progress_bps, 6250);
+}
+
+#[test]
+fn test_reopened_course_updates_stats() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, _admin) = setup(&env, 0);
+    let user = Address::generate(&env);
+
+    let module = courses.add_module(&course_id, &instructor, &String::from_str(&env, "Basics"));
+    let intro = courses.add_lesson(
+        &course_id, &instructor, &module, &String::from_str(&env, "Intro"),
+        &String::from_str(&env, "QmIntro"), &true, &15,
+    );
+    let extra = courses.add_lesson(
+        &course_id, &instructor, &module, &String::from_str(&env, "Extra"),
+        &String::from_str(&env, "QmExtra"), &false, &15,
+    );
+
+    assert!(client.complete_lesson(&user, &course_id, &intro).is_completed);
+    assert_eq!(client.get_course_stats(&course_id).completions, 1);
+
+    // Requiring the extra lesson reopens the course with the same lesson count
+    courses.update_lesson(&course_id, &instructor, &extra, &None, &None, &Some(true), &None);
+    assert!(!client.complete_lesson(&user, &course_id, &intro).is_completed);
+
+    let stats = client.get_course_stats(&course_id);
+    assert_eq!(stats.completions, 0);
+    assert_eq!(stats.active_learners, 1);
+    assert_eq!(stats.avg_progress_bps, 5000);
+}
+
+#[test]
+fn test_cohort_deadlines() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    let cohort_id = courses.create_cohort(
+        &course_id,
+        &instructor,
+        &0,
+        &1000,
+        &LatePolicy { grace_period: 100, penalty_percent: 20, hard_cutoff: Some(500) },
+    );
+    courses.set_lesson_due_date(&course_id, &instructor, &cohort_id, &0, &200);
+    courses.enroll_in_cohort(&course_id, &instructor, &cohort_id, &user);
+
+    // Lesson 0 is inside its grace period; lesson 1 falls back to the cohort end date
+    env.ledger().set_timestamp(250);
+    client.record_progress(&user, &course_id, &2);
+    env.ledger().set_timestamp(400);
+    client.complete_lesson(&user, &course_id, &3);
+
+    let late_lessons = client.get_late_lessons(&user, &course_id, &1);
+    assert_eq!(late_lessons.len(), 1);
+    assert_eq!(late_lessons.get(0).unwrap().lesson_index, 0);
+    assert_eq!(late_lessons.get(0).unwrap().status, DeadlineStatus::WithinGrace);
+
+    // A retake flags its own late lessons and leaves the first attempt's record alone
+    client.start_new_attempt(&user, &course_id);
+    client.complete_lesson(&user, &course_id, &1);
+    assert_eq!(client.get_late_lessons(&user, &course_id, &2).len(), 0);
+    assert_eq!(client.get_late_lessons(&user, &course_id, &1).len(), 1);
+
+    // Completing after the grace period costs the late penalty
+    env.ledger().set_timestamp(1200);
+    let completion_id = courses.record_completion(
+        &course_id,
+        &user,
+        &90,
+        &String::from_str(&env, "QmCertHash"),
+        &vec![&env],
+    );
+    let completion = courses.get_completion(&completion_id);
+    assert_eq!(completion.cohort_id, Some(cohort_id));
+    assert_eq!(completion.deadline_status, DeadlineStatus::Late);
+    assert_eq!(completion.final_grade, 72);
+}
+
+#[test]
+#[should_panic(expected = "Grade below pass mark")]
+fn test_late_penalty_applies_before_pass_mark() {
+    let env = Env::default();
+    let (_client, courses, course_id, instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    let cohort_id = courses.create_cohort(
+        &course_id,
+        &instructor,
+        &0,
+        &1000,
+        &LatePolicy { grace_period: 100, penalty_percent: 20, hard_cutoff: None },
+    );
+    courses.enroll_in_cohort(&course_id, &instructor, &cohort_id, &user);
+
+    let components = vec![
+        &env,
+        GradeComponent { id: 0, name: String::from_str(&env, "Final exam"), kind: GradeComponentKind::Exam, weight: 100, max_score: 100 },
+    ];
+    courses.set_gradebook(&course_id, &instructor, &components, &GradeRounding::Down, &70);
+    courses.record_score(&course_id, &instructor, &user, &0, &80);
+
+    // 80 passes on time, but the 20% late penalty leaves 64
+    env.ledger().set_timestamp(1200);
+    courses.record_completion(&course_id, &user, &0, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "Submission is past the cohort cutoff")]
+fn test_lesson_past_cutoff_rejected() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    let cohort_id = courses.create_cohort(
+        &course_id,
+        &instructor,
+        &0,
+        &1000,
+        &LatePolicy { grace_period: 100, penalty_percent: 20, hard_cutoff: Some(500) },
+    );
+    courses.set_lesson_due_date(&course_id, &instructor, &cohort_id, &0, &200);
+    courses.enroll_in_cohort(&course_id, &instructor, &cohort_id, &user);
+
+    env.ledger().set_timestamp(701);
+    client.complete_lesson(&user, &course_id, &0);
+}
+
+#[test]
+#[should_panic(expected = "Submission is past the cohort cutoff")]
+fn test_cohort_completion_past_cutoff_rejected() {
+    let env = Env::default();
+    let (_client, courses, course_id, instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    let cohort_id = courses.create_cohort(
+        &course_id,
+        &instructor,
+        &0,
+        &1000,
+        &LatePolicy { grace_period: 100, penalty_percent: 20, hard_cutoff: Some(500) },
+    );
+    courses.enroll_in_cohort(&course_id, &instructor, &cohort_id, &user);
+
+    env.ledger().set_timestamp(1600);
+    courses.record_completion(&course_id, &user, &90, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+}
+
+#[test]
+fn test_course_retake() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    client.set_attempt_policy(&course_id, &2, &AttemptScoring::Best);
+
+    env.ledger().set_timestamp(100);
+    client.record_progress(&user, &course_id, &4);
+    client.set_attempt_grade(&user, &course_id, &80);
+
+    env.ledger().set_timestamp(200);
+    assert_eq!(client.start_new_attempt(&user, &course_id), 2);
+    let progress = client.get_progress(&user, &course_id).unwrap();
+    assert_eq!(progress.lessons_completed, 0);
+    assert!(!progress.is_completed);
+    assert_eq!(client.get_course_stats(&course_id).completions, 0);
+
+    client.record_progress(&user, &course_id, &4);
+    client.set_attempt_grade(&user, &course_id, &65);
+
+    // The earlier attempt is kept and, being the better one, counts
+    let attempts = client.get_attempts(&user, &course_id);
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts.get(0).unwrap().grade, Some(80));
+    assert_eq!(attempts.get(0).unwrap().ended_at, Some(200));
+    assert!(attempts.get(0).unwrap().is_completed);
+    assert_eq!(client.get_counted_attempt(&user, &course_id).attempt, 1);
+
+    client.set_attempt_policy(&course_id, &2, &AttemptScoring::Latest);
+    assert_eq!(client.get_counted_attempt(&user, &course_id).grade, Some(65));
+}
+
+#[test]
+fn test_completion_graded_by_counted_attempt() {
+    let env = Env::default();
+    let (client, courses, course_id, instructor, admin) = setup(&env, 2);
+    let user = Address::generate(&env);
+    courses.set_progress_contract(&admin, &client.address);
+    client.set_attempt_policy(&course_id, &2, &AttemptScoring::Best);
+
+    let components = vec![
+        &env,
+        GradeComponent { id: 0, name: String::from_str(&env, "Final exam"), kind: GradeComponentKind::Exam, weight: 100, max_score: 100 },
+    ];
+    courses.set_gradebook(&course_id, &instructor, &components, &GradeRounding::Down, &50);
+
+    // Each attempt keeps the gradebook grade it was given
+    client.record_progress(&user, &course_id, &2);
+    courses.record_score(&course_id, &instructor, &user, &0, &90);
+    assert_eq!(client.grade_from_gradebook(&user, &course_id), 90);
+
+    client.start_new_attempt(&user, &course_id);
+    client.record_progress(&user, &course_id, &2);
+    courses.record_score(&course_id, &instructor, &user, &0, &55);
+    client.grade_from_gradebook(&user, &course_id);
+    assert_eq!(client.get_attempt(&user, &course_id, &1).grade, Some(90));
+
+    // The better first attempt counts, not the live gradebook score
+    let completion_id = courses.record_completion(&course_id, &user, &0, &String::from_str(&env, "QmCertHash"), &vec![&env]);
+    assert_eq!(courses.get_completion(&completion_id).final_grade, 90);
+}
+
+#[test]
+fn test_attestation_bound_to_attempt() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 5);
+    let user = Address::generate(&env);
+
+    let instructor_key = SigningKey::from_bytes(&[7; 32]);
+    let instructor_public = BytesN::from_array(&env, &instructor_key.verifying_key().to_bytes());
+    client.set_attestation_policy(&course_id, &AttestationMode::AllLessons, &vec![&env], &Some(instructor_public.clone()));
+
+    let payload = client.attestation_payload(&user, &course_id, &1, &0);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&env, &instructor_key.sign(&message).to_bytes());
+    client.complete_attested_lesson(&user, &course_id, &0, &instructor_public, &signature);
+
+    // The first attempt's signature cannot be replayed on a retake
+    assert_eq!(client.start_new_attempt(&user, &course_id), 2);
+    assert!(client.try_complete_attested_lesson(&user, &course_id, &0, &instructor_public, &signature).is_err());
+
+    let payload = client.attestation_payload(&user, &course_id, &2, &0);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&env, &instructor_key.sign(&message).to_bytes());
+    client.complete_attested_lesson(&user, &course_id, &0, &instructor_public, &signature);
+    assert!(client.is_lesson_completed(&user, &course_id, &0));
+}
+
+#[test]
+fn test_retake_history_and_milestones_per_attempt() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    client.set_course_milestones(&course_id, &vec![
+        &env,
+        Milestone { percent: 50, hook: MilestoneHook::EventOnly },
+        Milestone { percent: 100, hook: MilestoneHook::EventOnly },
+    ]);
+
+    env.ledger().set_timestamp(1000);
+    client.record_progress(&user, &course_id, &2);
+    env.ledger().set_timestamp(1000 + 7 * 24 * 60 * 60);
+    client.record_progress(&user, &course_id, &4);
+
+    env.ledger().set_timestamp(100_000_000);
+    client.start_new_attempt(&user, &course_id);
+    env.ledger().set_timestamp(100_000_000 + 14 * 24 * 60 * 60);
+    client.record_progress(&user, &course_id, &2);
+
+    // The first attempt keeps its own history and completion time
+    assert_eq!(client.get_history_length(&user, &course_id, &1), 2);
+    assert_eq!(client.get_pace(&user, &course_id, &1), 200);
+    assert_eq!(client.get_time_to_completion(&user, &course_id, &1), Some(7 * 24 * 60 * 60));
+    assert_eq!(client.get_milestones_reached(&user, &course_id, &1), vec![&env, 50, 100]);
+
+    // The retake starts from its reset snapshot
+    assert_eq!(client.get_history_length(&user, &course_id, &2), 2);
+    assert_eq!(client.get_pace(&user, &course_id, &2), 100);
+    assert_eq!(client.get_time_to_completion(&user, &course_id, &2), None);
+    assert_eq!(client.get_milestones_reached(&user, &course_id, &2), vec![&env, 50]);
+}
+
+#[test]
+#[should_panic(expected = "Attempt limit reached")]
+fn test_attempt_limit() {
+    let env = Env::default();
+    let (client, _courses, course_id, _instructor, _admin) = setup(&env, 4);
+    let user = Address::generate(&env);
+
+    client.set_attempt_policy(&course_id, &1, &AttemptScoring::Latest);
+    client.record_progress(&user, &course_id, &2);
+    client.start_new_attempt(&user, &course_id);
+}
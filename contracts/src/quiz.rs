@@ -0,0 +1,334 @@
+use soroban_sdk::{contract, contractimpl, contracttype, xdr::ToXdr, Address, BytesN, Env, String, Vec};
+use crate::courseMetadata::CourseMetadataContractClient;
+
+/// Upper bound on the number of questions in a quiz
+pub const MAX_QUESTIONS: u32 = 100;
+
+/// Upper bound on the points a single question can carry, keeping score arithmetic within u32
+pub const MAX_QUESTION_POINTS: u32 = 1000;
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Quiz {
+    pub id: u64,
+    pub course_id: String,
+    pub instructor: Address,
+    pub question_points: Vec<u32>, // points per question, in question order
+    pub answer_key_hash: BytesN<32>, // commitment_hash of the instructor's answer key
+    pub answer_key: Option<Vec<u32>>, // chosen option per question, set on reveal
+    pub submission_deadline: u64,
+    pub reveal_window: u64, // seconds learners have to reveal, counted from when the key is revealed
+    pub key_revealed_at: Option<u64>,
+    pub finalized_at: Option<u64>, // set once unrevealed submissions have been scored
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub learner: Address,
+    pub commitment: BytesN<32>,
+    pub submitted_at: u64,
+    pub answers: Option<Vec<u32>>, // set once the learner reveals
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuizResult {
+    pub quiz_id: u64,
+    pub learner: Address,
+    pub earned_points: u32,
+    pub total_points: u32,
+    pub correct_answers: u32,
+    pub score: u32, // 0-100, rounded down
+    pub graded_at: u64,
+}
+
+/// The answers and salt behind a commitment, hashed as XDR
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CommittedAnswers {
+    pub owner: Address, // the instructor for answer keys, the learner for submissions
+    pub answers: Vec<u32>,
+    pub salt: BytesN<32>,
+}
+
+#[contracttype]
+pub enum QuizKey {
+    Quiz(u64),
+    QuizCount,
+    Submission(u64, Address),
+    Submitters(u64),
+    Result(u64, Address),
+    Admin,
+    CourseMetadataContract,
+}
+
+#[contract]
+pub struct QuizContract;
+
+#[contractimpl]
+impl QuizContract {
+    /// Initialize the contract with an admin and the CourseMetadataContract quizzes belong to
+    pub fn initialize(env: Env, admin: Address, course_metadata_contract: Address) {
+        if env.storage().instance().has(&QuizKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&QuizKey::Admin, &admin);
+        env.storage().instance().set(&QuizKey::CourseMetadataContract, &course_metadata_contract);
+        env.storage().instance().set(&QuizKey::QuizCount, &0u64);
+    }
+
+    /// Create a quiz, committing to its answer key without revealing it (course instructor only)
+    pub fn create_quiz(
+        env: Env,
+        instructor: Address,
+        course_id: String,
+        question_points: Vec<u32>,
+        answer_key_hash: BytesN<32>,
+        submission_deadline: u64,
+        reveal_window: u64,
+    ) -> u64 {
+        instructor.require_auth();
+
+        let course_contract: Address = env.storage().instance()
+            .get(&QuizKey::CourseMetadataContract)
+            .unwrap_or_else(|| panic!("Contract not initialized"));
+        let course = match CourseMetadataContractClient::new(&env, &course_contract).try_get_course(&course_id) {
+            Ok(Ok(course)) => course,
+            _ => panic!("Course not found"),
+        };
+        if course.instructor != instructor {
+            panic!("Only course instructor can create quizzes");
+        }
+
+        if question_points.is_empty() || question_points.len() > MAX_QUESTIONS {
+            panic!("Question count out of range");
+        }
+        if question_points.iter().any(|points| points > MAX_QUESTION_POINTS) {
+            panic!("Question points out of range");
+        }
+        if submission_deadline <= env.ledger().timestamp() || reveal_window == 0 {
+            panic!("Invalid quiz deadlines");
+        }
+
+        let quiz_id: u64 = env.storage().instance()
+            .get(&QuizKey::QuizCount)
+            .unwrap_or(0) + 1;
+        let quiz = Quiz {
+            id: quiz_id,
+            course_id,
+            instructor,
+            question_points,
+            answer_key_hash,
+            answer_key: None,
+            submission_deadline,
+            reveal_window,
+            key_revealed_at: None,
+            finalized_at: None,
+            created_at: env.ledger().timestamp(),
+        };
+
+        env.storage().persistent().set(&QuizKey::Quiz(quiz_id), &quiz);
+        env.storage().instance().set(&QuizKey::QuizCount, &quiz_id);
+
+        quiz_id
+    }
+
+    /// Commit to a set of answers before the submission deadline
+    pub fn submit_answers(env: Env, learner: Address, quiz_id: u64, commitment: BytesN<32>) {
+        learner.require_auth();
+
+        let quiz = Self::get_quiz(env.clone(), quiz_id);
+        let now = env.ledger().timestamp();
+        if now > quiz.submission_deadline {
+            panic!("Submission deadline has passed");
+        }
+
+        let submission_key = QuizKey::Submission(quiz_id, learner.clone());
+        if env.storage().persistent().has(&submission_key) {
+            panic!("Answers already submitted");
+        }
+
+        let mut submitters = Self::get_submitters(env.clone(), quiz_id);
+        submitters.push_back(learner.clone());
+        env.storage().persistent().set(&QuizKey::Submitters(quiz_id), &submitters);
+
+        let submission = Submission {
+            learner,
+            commitment,
+            submitted_at: now,
+            answers: None,
+        };
+        env.storage().persistent().set(&submission_key, &submission);
+    }
+
+    /// Reveal the answer key after the submission deadline (quiz instructor only)
+    pub fn reveal_answer_key(env: Env, quiz_id: u64, answer_key: Vec<u32>, salt: BytesN<32>) {
+        let mut quiz = Self::get_quiz(env.clone(), quiz_id);
+        quiz.instructor.require_auth();
+
+        let now = env.ledger().timestamp();
+        if now <= quiz.submission_deadline {
+            panic!("Submissions are still open");
+        }
+        if now > Self::get_key_reveal_deadline(env.clone(), quiz_id) {
+            panic!("Key reveal deadline has passed");
+        }
+        if quiz.answer_key.is_some() {
+            panic!("Answer key already revealed");
+        }
+        if answer_key.len() != quiz.question_points.len() {
+            panic!("Answer count does not match questions");
+        }
+        if Self::commitment_hash(env.clone(), quiz.instructor.clone(), answer_key.clone(), salt) != quiz.answer_key_hash {
+            panic!("Answer key does not match commitment");
+        }
+
+        quiz.answer_key = Some(answer_key);
+        quiz.key_revealed_at = Some(now);
+        env.storage().persistent().set(&QuizKey::Quiz(quiz_id), &quiz);
+    }
+
+    /// Reveal committed answers once the key is public; the submission is graded immediately
+    pub fn reveal_answers(env: Env, learner: Address, quiz_id: u64, answers: Vec<u32>, salt: BytesN<32>) -> QuizResult {
+        learner.require_auth();
+
+        let quiz = Self::get_quiz(env.clone(), quiz_id);
+        let answer_key = quiz.answer_key.clone().unwrap_or_else(|| panic!("Answer key not revealed"));
+        let now = env.ledger().timestamp();
+        if now > Self::get_answer_reveal_deadline(env.clone(), quiz_id).unwrap() {
+            panic!("Reveal deadline has passed");
+        }
+
+        let submission_key = QuizKey::Submission(quiz_id, learner.clone());
+        let mut submission: Submission = env.storage().persistent()
+            .get(&submission_key)
+            .unwrap_or_else(|| panic!("Submission not found"));
+        if submission.answers.is_some() {
+            panic!("Answers already revealed");
+        }
+        if answers.len() != quiz.question_points.len() {
+            panic!("Answer count does not match questions");
+        }
+        if Self::commitment_hash(env.clone(), learner.clone(), answers.clone(), salt) != submission.commitment {
+            panic!("Answers do not match commitment");
+        }
+
+        let mut earned_points = 0u32;
+        let mut total_points = 0u32;
+        let mut correct_answers = 0u32;
+        for (index, points) in quiz.question_points.iter().enumerate() {
+            total_points += points;
+            if answers.get(index as u32) == answer_key.get(index as u32) {
+                earned_points += points;
+                correct_answers += 1;
+            }
+        }
+
+        let result = QuizResult {
+            quiz_id,
+            learner: learner.clone(),
+            earned_points,
+            total_points,
+            correct_answers,
+            score: (earned_points * 100).checked_div(total_points).unwrap_or(0),
+            graded_at: now,
+        };
+
+        submission.answers = Some(answers);
+        env.storage().persistent().set(&submission_key, &submission);
+        env.storage().persistent().set(&QuizKey::Result(quiz_id, learner), &result);
+
+        result
+    }
+
+    /// Score every submission still unrevealed after the reveal deadline as 0; anyone may call it once
+    pub fn finalize_quiz(env: Env, quiz_id: u64) -> u32 {
+        let mut quiz = Self::get_quiz(env.clone(), quiz_id);
+        let now = env.ledger().timestamp();
+        let reveal_deadline = Self::get_answer_reveal_deadline(env.clone(), quiz_id)
+            .unwrap_or_else(|| panic!("Answer key not revealed"));
+        if now <= reveal_deadline {
+            panic!("Reveal window still open");
+        }
+        if quiz.finalized_at.is_some() {
+            panic!("Quiz already finalized");
+        }
+
+        let mut total_points = 0u32;
+        for points in quiz.question_points.iter() {
+            total_points += points;
+        }
+
+        let mut unrevealed = 0u32;
+        for learner in Self::get_submitters(env.clone(), quiz_id).iter() {
+            let result_key = QuizKey::Result(quiz_id, learner.clone());
+            if env.storage().persistent().has(&result_key) {
+                continue;
+            }
+
+            let result = QuizResult {
+                quiz_id,
+                learner,
+                earned_points: 0,
+                total_points,
+                correct_answers: 0,
+                score: 0,
+                graded_at: now,
+            };
+            env.storage().persistent().set(&result_key, &result);
+            unrevealed += 1;
+        }
+
+        quiz.finalized_at = Some(now);
+        env.storage().persistent().set(&QuizKey::Quiz(quiz_id), &quiz);
+
+        unrevealed
+    }
+
+    /// Hash a set of answers with a salt; used for both answer keys and learner submissions
+    pub fn commitment_hash(env: Env, owner: Address, answers: Vec<u32>, salt: BytesN<32>) -> BytesN<32> {
+        let committed = CommittedAnswers { owner, answers, salt };
+        env.crypto().sha256(&committed.to_xdr(&env)).into()
+    }
+
+    /// Get a quiz
+    pub fn get_quiz(env: Env, quiz_id: u64) -> Quiz {
+        env.storage().persistent()
+            .get(&QuizKey::Quiz(quiz_id))
+            .unwrap_or_else(|| panic!("Quiz not found"))
+    }
+
+    /// Get the deadline for the instructor to reveal the answer key: one reveal window after submissions close
+    ///
+    /// A quiz whose key is not revealed by then cannot be graded
+    pub fn get_key_reveal_deadline(env: Env, quiz_id: u64) -> u64 {
+        let quiz = Self::get_quiz(env, quiz_id);
+        quiz.submission_deadline.saturating_add(quiz.reveal_window)
+    }
+
+    /// Get the deadline for learners to reveal answers: the full reveal window counted from when the key was revealed
+    pub fn get_answer_reveal_deadline(env: Env, quiz_id: u64) -> Option<u64> {
+        let quiz = Self::get_quiz(env, quiz_id);
+        quiz.key_revealed_at.map(|revealed_at| revealed_at.saturating_add(quiz.reveal_window))
+    }
+
+    /// Get a learner's submission to a quiz
+    pub fn get_submission(env: Env, quiz_id: u64, learner: Address) -> Option<Submission> {
+        env.storage().persistent().get(&QuizKey::Submission(quiz_id, learner))
+    }
+
+    /// Get the learners who submitted to a quiz, in submission order
+    pub fn get_submitters(env: Env, quiz_id: u64) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&QuizKey::Submitters(quiz_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get a learner's graded result, once they have revealed their answers
+    pub fn get_result(env: Env, quiz_id: u64, learner: Address) -> Option<QuizResult> {
+        env.storage().persistent().get(&QuizKey::Result(quiz_id, learner))
+    }
+}
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use crate::quiz::{QuizContract, QuizContractClient, MAX_QUESTION_POINTS};
+use crate::courseMetadata::{CourseMetadataContract, CourseMetadataContractClient, NewCourse};
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Address, BytesN, Env, String};
+
+/// Register the quiz contract and create a three-question quiz with answer key [2, 0, 1]
+fn setup<'a>(env: &Env) -> (QuizContractClient<'a>, u64, Address) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let instructor = Address::generate(env);
+
+    let courses = CourseMetadataContractClient::new(env, &env.register_contract(None, CourseMetadataContract));
+    courses.initialize(&admin);
+    let course_id = courses.create_course(
+        &instructor,
+        &NewCourse {
+            title: String::from_str(env, "Soroban Basics"),
+            description: String::from_str(env, "Smart contracts on Stellar"),
+            category: String::from_str(env, "Programming"),
+            level: String::from_str(env, "beginner"),
+            duration: 10,
+            price: 0,
+            prerequisites: vec![env],
+            learning_objectives: vec![env],
+            syllabus: String::from_str(env, "QmSyllabus"),
+            thumbnail_url: String::from_str(env, "https://example.com/thumbnail.jpg"),
+            tags: vec![env],
+            language: String::from_str(env, "English"),
+            certificate_enabled: true,
+            max_students: 100,
+        },
+    );
+
+    let client = QuizContractClient::new(env, &env.register_contract(None, QuizContract));
+    client.initialize(&admin, &courses.address);
+
+    let answer_key_hash = client.commitment_hash(&instructor, &vec![env, 2, 0, 1], &BytesN::from_array(env, &[9; 32]));
+    let quiz_id = client.create_quiz(&instructor, &course_id, &vec![env, 1, 1, 2], &answer_key_hash, &100, &100);
+
+    (client, quiz_id, instructor)
+}
+
+#[test]
+fn test_commit_reveal_grading() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    let learner = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[5; 32]);
+
+    let commitment = client.commitment_hash(&learner, &vec![&env, 2, 1, 1], &salt);
+    client.submit_answers(&learner, &quiz_id, &commitment);
+    assert_eq!(client.get_submitters(&quiz_id), vec![&env, learner.clone()]);
+
+    env.ledger().set_timestamp(150);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 2, 0, 1], &BytesN::from_array(&env, &[9; 32]));
+
+    // Questions 1 and 3 are right: 3 of 4 points
+    let result = client.reveal_answers(&learner, &quiz_id, &vec![&env, 2, 1, 1], &salt);
+    assert_eq!(result.earned_points, 3);
+    assert_eq!(result.total_points, 4);
+    assert_eq!(result.correct_answers, 2);
+    assert_eq!(result.score, 75);
+    assert_eq!(client.get_result(&quiz_id, &learner), Some(result));
+}
+
+#[test]
+#[should_panic(expected = "Answers do not match commitment")]
+fn test_changed_answers_rejected() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    let learner = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[5; 32]);
+
+    client.submit_answers(&learner, &quiz_id, &client.commitment_hash(&learner, &vec![&env, 0, 0, 0], &salt));
+
+    env.ledger().set_timestamp(150);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 2, 0, 1], &BytesN::from_array(&env, &[9; 32]));
+    client.reveal_answers(&learner, &quiz_id, &vec![&env, 2, 0, 1], &salt);
+}
+
+#[test]
+#[should_panic(expected = "Answer key does not match commitment")]
+fn test_changed_answer_key_rejected() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+
+    env.ledger().set_timestamp(150);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 1, 1, 1], &BytesN::from_array(&env, &[9; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Submission deadline has passed")]
+fn test_late_submission_rejected() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    let learner = Address::generate(&env);
+
+    env.ledger().set_timestamp(101);
+    client.submit_answers(&learner, &quiz_id, &BytesN::from_array(&env, &[1; 32]));
+}
+
+#[test]
+fn test_reveal_window_starts_at_key_reveal() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    let learner = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[5; 32]);
+
+    client.submit_answers(&learner, &quiz_id, &client.commitment_hash(&learner, &vec![&env, 2, 0, 1], &salt));
+    assert_eq!(client.get_answer_reveal_deadline(&quiz_id), None);
+
+    // A key revealed late still leaves learners the full 100-second window
+    env.ledger().set_timestamp(190);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 2, 0, 1], &BytesN::from_array(&env, &[9; 32]));
+    assert_eq!(client.get_answer_reveal_deadline(&quiz_id), Some(290));
+
+    env.ledger().set_timestamp(250);
+    assert_eq!(client.reveal_answers(&learner, &quiz_id, &vec![&env, 2, 0, 1], &salt).score, 100);
+}
+
+#[test]
+#[should_panic(expected = "Reveal deadline has passed")]
+fn test_reveal_after_window_rejected() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    let learner = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[5; 32]);
+
+    client.submit_answers(&learner, &quiz_id, &client.commitment_hash(&learner, &vec![&env, 2, 0, 1], &salt));
+    env.ledger().set_timestamp(150);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 2, 0, 1], &BytesN::from_array(&env, &[9; 32]));
+
+    env.ledger().set_timestamp(251);
+    client.reveal_answers(&learner, &quiz_id, &vec![&env, 2, 0, 1], &salt);
+}
+
+#[test]
+#[should_panic(expected = "Question points out of range")]
+fn test_question_points_capped() {
+    let env = Env::default();
+    let (client, quiz_id, instructor) = setup(&env);
+    let quiz = client.get_quiz(&quiz_id);
+
+    client.create_quiz(
+        &instructor,
+        &quiz.course_id,
+        &vec![&env, MAX_QUESTION_POINTS + 1],
+        &quiz.answer_key_hash,
+        &100,
+        &100,
+    );
+}
+
+#[test]
+fn test_finalize_scores_unrevealed_submissions() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    let revealed = Address::generate(&env);
+    let silent = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[5; 32]);
+
+    client.submit_answers(&revealed, &quiz_id, &client.commitment_hash(&revealed, &vec![&env, 2, 0, 1], &salt));
+    client.submit_answers(&silent, &quiz_id, &client.commitment_hash(&silent, &vec![&env, 2, 0, 1], &salt));
+
+    env.ledger().set_timestamp(150);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 2, 0, 1], &BytesN::from_array(&env, &[9; 32]));
+    client.reveal_answers(&revealed, &quiz_id, &vec![&env, 2, 0, 1], &salt);
+
+    // Not before the learners' window closes
+    assert!(client.try_finalize_quiz(&quiz_id).is_err());
+
+    env.ledger().set_timestamp(251);
+    assert_eq!(client.finalize_quiz(&quiz_id), 1);
+    assert_eq!(client.get_result(&quiz_id, &revealed).unwrap().score, 100);
+    let result = client.get_result(&quiz_id, &silent).unwrap();
+    assert_eq!(result.score, 0);
+    assert_eq!(result.total_points, 4);
+    assert_eq!(client.get_quiz(&quiz_id).finalized_at, Some(251));
+
+    assert!(client.try_finalize_quiz(&quiz_id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Key reveal deadline has passed")]
+fn test_answer_key_reveal_deadline() {
+    let env = Env::default();
+    let (client, quiz_id, _instructor) = setup(&env);
+    assert_eq!(client.get_key_reveal_deadline(&quiz_id), 200);
+
+    env.ledger().set_timestamp(201);
+    client.reveal_answer_key(&quiz_id, &vec![&env, 2, 0, 1], &BytesN::from_array(&env, &[9; 32]));
+}
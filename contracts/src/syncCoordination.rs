@@ -1,5 +1,8 @@
-#![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec, Map, Symbol, U256};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use crate::numbered_id;
+
+/// Upper bound on the byte length of a payload produced by merging two entries
+pub const MAX_MERGED_PAYLOAD_LEN: usize = 1024;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -11,7 +14,7 @@ pub enum DeviceType {
 }
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SyncStatus {
     Pending,
     InProgress,
@@ -21,8 +24,9 @@ pub enum SyncStatus {
 }
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ConflictResolution {
+    Unresolved,
     LastWriteWins,
     ManualReview,
     MergeData,
@@ -55,7 +59,7 @@ pub struct SyncEntry {
     pub data_hash: String, // Hash of the data being synced
     pub timestamp: u64,
     pub sync_status: SyncStatus,
-    pub conflict_resolution: Option<ConflictResolution>,
+    pub conflict_resolution: ConflictResolution,
     pub parent_entry_id: Option<String>, // For conflict resolution
     pub merged_with: Vec<String>, // Entry IDs this was merged with
     pub payload: String, // Actual data (simplified - in production use IPFS)
@@ -70,7 +74,7 @@ pub struct SyncConflict {
     pub entry_id_2: String,
     pub conflict_type: String, // "timestamp", "data", "version"
     pub detected_at: u64,
-    pub resolution: Option<ConflictResolution>,
+    pub resolution: ConflictResolution,
     pub resolved_at: Option<u64>,
     pub resolved_by: Option<Address>, // Admin or automated
     pub winning_entry_id: Option<String>,
@@ -134,7 +138,7 @@ impl SyncCoordinationContract {
             .get(&SyncCoordinationKey::DeviceCount)
             .unwrap_or(0);
         
-        let device_id = format!("device_{}", device_count + 1);
+        let device_id = numbered_id(&env, "device_", device_count + 1);
         let timestamp = env.ledger().timestamp();
         
         let device = Device {
@@ -154,7 +158,7 @@ impl SyncCoordinationContract {
         env.storage().instance().set(&SyncCoordinationKey::DeviceCount, &(device_count + 1));
 
         // Add to user's device list
-        let mut user_devices = Self::get_user_devices(env, user_address.clone());
+        let mut user_devices = Self::get_user_devices(env.clone(), user_address.clone());
         user_devices.push_back(device_id.clone());
         env.storage().instance().set(&SyncCoordinationKey::UserDevices(user_address), &user_devices);
 
@@ -168,7 +172,7 @@ impl SyncCoordinationContract {
         device_id: String,
     ) -> String {
         // Verify device exists and belongs to user
-        let device = Self::get_device(env, device_id.clone());
+        let device = Self::get_device(env.clone(), device_id.clone());
         if device.user_address != user_address {
             panic!("Device does not belong to user");
         }
@@ -181,7 +185,7 @@ impl SyncCoordinationContract {
             .get(&SyncCoordinationKey::SessionCount)
             .unwrap_or(0);
         
-        let session_id = format!("session_{}", session_count + 1);
+        let session_id = numbered_id(&env, "session_", session_count + 1);
         let timestamp = env.ledger().timestamp();
 
         let session = SyncSession {
@@ -217,7 +221,7 @@ impl SyncCoordinationContract {
         payload: String,
     ) -> String {
         // Verify session exists and is active
-        let session = Self::get_sync_session(env, session_id.clone());
+        let session = Self::get_sync_session(env.clone(), session_id.clone());
         if session.status != SyncStatus::InProgress {
             panic!("Session is not active");
         }
@@ -235,7 +239,7 @@ impl SyncCoordinationContract {
             .get(&SyncCoordinationKey::EntryCount)
             .unwrap_or(0);
         
-        let entry_id = format!("entry_{}", entry_count + 1);
+        let entry_id = numbered_id(&env, "entry_", entry_count + 1);
         let timestamp = env.ledger().timestamp();
 
         let sync_entry = SyncEntry {
@@ -246,7 +250,7 @@ impl SyncCoordinationContract {
             data_hash: data_hash.clone(),
             timestamp,
             sync_status: if conflict_id.is_some() { SyncStatus::Conflict } else { SyncStatus::Completed },
-            conflict_resolution: None, // Will be set during conflict resolution
+            conflict_resolution: ConflictResolution::Unresolved, // Will be set during conflict resolution
             parent_entry_id: None,
             merged_with: Vec::new(&env),
             payload,
@@ -274,7 +278,7 @@ impl SyncCoordinationContract {
         winning_entry_id: String,
         resolver: Address,
     ) -> bool {
-        let mut conflict = Self::get_sync_conflict(env, conflict_id.clone());
+        let mut conflict = Self::get_sync_conflict(env.clone(), conflict_id.clone());
         
         // Verify resolver is authorized (admin or conflict owner)
         let admin: Address = env.storage().instance()
@@ -287,30 +291,31 @@ impl SyncCoordinationContract {
 
         // Apply resolution strategy
         match resolution {
+            ConflictResolution::Unresolved => panic!("Resolution strategy required"),
             ConflictResolution::LastWriteWins => {
                 // Keep the entry with latest timestamp
-                Self::apply_last_write_wins(env, &conflict, winning_entry_id);
+                Self::apply_last_write_wins(env.clone(), &conflict, winning_entry_id.clone());
             },
             ConflictResolution::FirstWriteWins => {
                 // Keep the entry with earliest timestamp
-                Self::apply_first_write_wins(env, &conflict, winning_entry_id);
+                Self::apply_first_write_wins(env.clone(), &conflict, winning_entry_id.clone());
             },
             ConflictResolution::TimestampWins => {
                 // Use timestamp as tiebreaker
-                Self::apply_timestamp_wins(env, &conflict, winning_entry_id);
+                Self::apply_timestamp_wins(env.clone(), &conflict, winning_entry_id.clone());
             },
             ConflictResolution::ManualReview => {
                 // Mark for manual review
-                Self::apply_manual_review(env, &conflict, winning_entry_id);
+                Self::apply_manual_review(env.clone(), &conflict, winning_entry_id.clone());
             },
             ConflictResolution::MergeData => {
                 // Attempt to merge conflicting entries
-                Self::apply_merge_data(env, &conflict, winning_entry_id);
+                Self::apply_merge_data(env.clone(), &conflict, winning_entry_id.clone());
             },
         }
 
         // Update conflict record
-        conflict.resolution = Some(resolution.clone());
+        conflict.resolution = resolution;
         conflict.resolved_at = Some(env.ledger().timestamp());
         conflict.resolved_by = Some(resolver.clone());
         conflict.winning_entry_id = Some(winning_entry_id);
@@ -327,7 +332,7 @@ impl SyncCoordinationContract {
         success: bool,
         error_message: Option<String>,
     ) -> bool {
-        let mut session = Self::get_sync_session(env, session_id.clone());
+        let mut session = Self::get_sync_session(env.clone(), session_id.clone());
         
         if session.status != SyncStatus::InProgress {
             panic!("Session is not in progress");
@@ -340,7 +345,7 @@ impl SyncCoordinationContract {
         env.storage().instance().set(&SyncCoordinationKey::SyncSession(session_id), &session);
 
         // Update device last sync
-        let mut device = Self::get_device(env, session.device_id.clone());
+        let mut device = Self::get_device(env.clone(), session.device_id.clone());
         device.last_sync = env.ledger().timestamp();
         device.sync_version += 1;
         env.storage().instance().set(&SyncCoordinationKey::Device(session.device_id), &device);
@@ -384,14 +389,14 @@ impl SyncCoordinationContract {
     }
 
     /// Get user's sync history
-    pub fn get_user_sync_history(env: Env, user_address: Address, limit: u32) -> Vec<String> {
+    pub fn get_user_sync_history(env: Env, _user_address: Address, _limit: u32) -> Vec<String> {
         // This is a simplified implementation
         // In production, you'd maintain an index by user and timestamp
         Vec::new(&env)
     }
 
     /// Get conflicts for user
-    pub fn get_user_conflicts(env: Env, user_address: Address) -> Vec<String> {
+    pub fn get_user_conflicts(env: Env, _user_address: Address) -> Vec<String> {
         // Simplified implementation
         // In production, maintain user conflict index
         Vec::new(&env)
@@ -399,7 +404,7 @@ impl SyncCoordinationContract {
 
     /// Deactivate a device
     pub fn deactivate_device(env: Env, user_address: Address, device_id: String) -> bool {
-        let mut device = Self::get_device(env, device_id.clone());
+        let mut device = Self::get_device(env.clone(), device_id.clone());
         
         if device.user_address != user_address {
             panic!("Device does not belong to user");
@@ -419,7 +424,7 @@ impl SyncCoordinationContract {
         device_id: String,
         capabilities: Vec<String>,
     ) -> bool {
-        let mut device = Self::get_device(env, device_id.clone());
+        let mut device = Self::get_device(env.clone(), device_id.clone());
         
         if device.user_address != user_address {
             panic!("Device does not belong to user");
@@ -434,11 +439,11 @@ impl SyncCoordinationContract {
 
     /// Check for conflicts with existing entries
     fn check_for_conflicts(
-        env: Env,
-        user_address: Address,
-        data_type: String,
-        data_hash: String,
-        timestamp: u64,
+        _env: Env,
+        _user_address: Address,
+        _data_type: String,
+        _data_hash: String,
+        _timestamp: u64,
     ) -> Option<String> {
         // Simplified conflict detection
         // In production, this would check against recent entries of same data type
@@ -447,54 +452,63 @@ impl SyncCoordinationContract {
     }
 
     /// Apply last-write-wins resolution
-    fn apply_last_write_wins(env: Env, conflict: &SyncConflict, winning_entry_id: String) {
+    fn apply_last_write_wins(env: Env, _conflict: &SyncConflict, winning_entry_id: String) {
         // Update the winning entry and mark others as superseded
-        let winning_entry = Self::get_sync_entry(env, winning_entry_id.clone());
+        let winning_entry = Self::get_sync_entry(env.clone(), winning_entry_id.clone());
         let mut updated_entry = winning_entry;
         updated_entry.sync_status = SyncStatus::Completed;
-        updated_entry.conflict_resolution = Some(ConflictResolution::LastWriteWins);
+        updated_entry.conflict_resolution = ConflictResolution::LastWriteWins;
         env.storage().instance().set(&SyncCoordinationKey::SyncEntry(winning_entry_id), &updated_entry);
     }
 
     /// Apply first-write-wins resolution
-    fn apply_first_write_wins(env: Env, conflict: &SyncConflict, winning_entry_id: String) {
-        let winning_entry = Self::get_sync_entry(env, winning_entry_id.clone());
+    fn apply_first_write_wins(env: Env, _conflict: &SyncConflict, winning_entry_id: String) {
+        let winning_entry = Self::get_sync_entry(env.clone(), winning_entry_id.clone());
         let mut updated_entry = winning_entry;
         updated_entry.sync_status = SyncStatus::Completed;
-        updated_entry.conflict_resolution = Some(ConflictResolution::FirstWriteWins);
+        updated_entry.conflict_resolution = ConflictResolution::FirstWriteWins;
         env.storage().instance().set(&SyncCoordinationKey::SyncEntry(winning_entry_id), &updated_entry);
     }
 
     /// Apply timestamp-wins resolution
-    fn apply_timestamp_wins(env: Env, conflict: &SyncConflict, winning_entry_id: String) {
-        let winning_entry = Self::get_sync_entry(env, winning_entry_id.clone());
+    fn apply_timestamp_wins(env: Env, _conflict: &SyncConflict, winning_entry_id: String) {
+        let winning_entry = Self::get_sync_entry(env.clone(), winning_entry_id.clone());
         let mut updated_entry = winning_entry;
         updated_entry.sync_status = SyncStatus::Completed;
-        updated_entry.conflict_resolution = Some(ConflictResolution::TimestampWins);
+        updated_entry.conflict_resolution = ConflictResolution::TimestampWins;
         env.storage().instance().set(&SyncCoordinationKey::SyncEntry(winning_entry_id), &updated_entry);
     }
 
     /// Apply manual review resolution
-    fn apply_manual_review(env: Env, conflict: &SyncConflict, winning_entry_id: String) {
-        let winning_entry = Self::get_sync_entry(env, winning_entry_id.clone());
+    fn apply_manual_review(env: Env, _conflict: &SyncConflict, winning_entry_id: String) {
+        let winning_entry = Self::get_sync_entry(env.clone(), winning_entry_id.clone());
         let mut updated_entry = winning_entry;
         updated_entry.sync_status = SyncStatus::Pending; // Requires manual review
-        updated_entry.conflict_resolution = Some(ConflictResolution::ManualReview);
+        updated_entry.conflict_resolution = ConflictResolution::ManualReview;
         env.storage().instance().set(&SyncCoordinationKey::SyncEntry(winning_entry_id), &updated_entry);
     }
 
     /// Apply merge data resolution
     fn apply_merge_data(env: Env, conflict: &SyncConflict, winning_entry_id: String) {
-        let winning_entry = Self::get_sync_entry(env, winning_entry_id.clone());
-        let other_entry = Self::get_sync_entry(env, conflict.entry_id_2.clone());
+        let winning_entry = Self::get_sync_entry(env.clone(), winning_entry_id.clone());
+        let other_entry = Self::get_sync_entry(env.clone(), conflict.entry_id_2.clone());
         
-        // Simple merge: combine payloads
-        let merged_payload = format!("{}|{}", winning_entry.payload, other_entry.payload);
+        // Simple merge: combine payloads as "<winning>|<other>"
+        let winning_len = winning_entry.payload.len() as usize;
+        let other_len = other_entry.payload.len() as usize;
+        if winning_len + 1 + other_len > MAX_MERGED_PAYLOAD_LEN {
+            panic!("Merged payload too long");
+        }
+        let mut merged_buf = [0u8; MAX_MERGED_PAYLOAD_LEN];
+        winning_entry.payload.copy_into_slice(&mut merged_buf[..winning_len]);
+        merged_buf[winning_len] = b'|';
+        other_entry.payload.copy_into_slice(&mut merged_buf[winning_len + 1..winning_len + 1 + other_len]);
+        let merged_payload = String::from_bytes(&env, &merged_buf[..winning_len + 1 + other_len]);
         
         let mut updated_entry = winning_entry;
         updated_entry.payload = merged_payload;
         updated_entry.sync_status = SyncStatus::Completed;
-        updated_entry.conflict_resolution = Some(ConflictResolution::MergeData);
+        updated_entry.conflict_resolution = ConflictResolution::MergeData;
         updated_entry.merged_with.push_back(conflict.entry_id_2.clone());
         
         env.storage().instance().set(&SyncCoordinationKey::SyncEntry(winning_entry_id), &updated_entry);
@@ -529,7 +543,7 @@ impl SyncCoordinationContract {
     }
 
     /// Clean up old sync data (maintenance function)
-    pub fn cleanup_old_data(env: Env, older_than: u64) -> u64 {
+    pub fn cleanup_old_data(_env: Env, _older_than: u64) -> u64 {
         // This would require iterating through all entries and removing old ones
         // Simplified implementation for demo
         0